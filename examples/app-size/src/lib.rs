@@ -0,0 +1,15 @@
+//! A minimal frontend to measure the `.wasm` size
+//! with no features and with all features enabled.
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub async fn run() -> Result<(), tauri_wasm::Error> {
+    tauri_wasm::invoke("hello").with_args(&b"data"[..]).await?;
+    tauri_wasm::emit_str("size", "minimal").await?;
+
+    #[cfg(feature = "full")]
+    tauri_wasm::emit("size", &("full", 1))?.await?;
+
+    Ok(())
+}
@@ -1,4 +1,10 @@
-use tauri::{Window, ipc::Request};
+use {
+    std::{collections::HashMap, sync::Mutex},
+    tauri::{
+        Window,
+        ipc::{InvokeBody, Request},
+    },
+};
 
 #[tauri::command]
 fn hello(message: String) -> String {
@@ -25,6 +31,34 @@ fn headers(req: Request<'_>) -> String {
     values.join(".")
 }
 
+#[tauri::command]
+fn bytes(req: Request<'_>) -> Result<usize, &'static str> {
+    match req.body() {
+        InvokeBody::Raw(data) => Ok(data.len()),
+        InvokeBody::Json(_) => Err("expected raw bytes"),
+    }
+}
+
+#[tauri::command]
+fn unit() {}
+
+#[tauri::command]
+fn check_user() -> Result<(), &'static str> {
+    Err("Username not allowed")
+}
+
+#[tauri::command]
+fn sum(values: HashMap<String, u32>) -> u32 {
+    values.values().sum()
+}
+
+static LAST_EVENT: Mutex<String> = Mutex::new(String::new());
+
+#[tauri::command]
+fn last_event() -> String {
+    LAST_EVENT.lock().expect("lock the last event").clone()
+}
+
 #[tauri::command]
 fn close(window: Window) {
     _ = window.close();
@@ -48,11 +82,14 @@ pub fn run() {
             webview.listen("test-event", |event| {
                 let payload = event.payload();
                 println!("test-event: {payload}");
+                *LAST_EVENT.lock().expect("lock the last event") = payload.to_owned();
             });
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![hello, headers, close])
+        .invoke_handler(tauri::generate_handler![
+            hello, headers, bytes, unit, check_user, sum, last_event, close
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
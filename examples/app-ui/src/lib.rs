@@ -1,4 +1,8 @@
-use {gloo::console, serde::Serialize, wasm_bindgen::prelude::*};
+use {
+    gloo::console,
+    tauri_wasm::{EventTarget, ToStringValue},
+    wasm_bindgen::prelude::*,
+};
 
 async fn hello() -> Result<(), JsError> {
     use serde::Serialize;
@@ -11,7 +15,7 @@ async fn hello() -> Result<(), JsError> {
     let s = Send { message: "ping" };
 
     let args = tauri_wasm::args(&s)?;
-    let message = tauri_wasm::invoke("hello").with_args(args).await?;
+    let message = tauri_wasm::invoke("hello").with_args(args).text().await?;
     console::log!("message from backend received", &message);
 
     if message == "ping" {
@@ -21,6 +25,27 @@ async fn hello() -> Result<(), JsError> {
     }
 }
 
+async fn response() -> Result<(), JsError> {
+    use tauri_wasm::invoke::JsResponse;
+
+    let args = tauri_wasm::named_args("message", "ping")?;
+    let res = tauri_wasm::invoke("hello")
+        .with_args(args)
+        .returning::<JsResponse>()
+        .await?;
+
+    if res.as_str().as_deref() != Some("ping") {
+        return Err(JsError::new("wrong backend response"));
+    }
+
+    let res = tauri_wasm::invoke("unit").response().await?;
+    if res.is_null() {
+        Ok(())
+    } else {
+        Err(JsError::new("unit response should be null"))
+    }
+}
+
 async fn headers() -> Result<(), JsError> {
     use tauri_wasm::invoke::Options;
 
@@ -30,7 +55,10 @@ async fn headers() -> Result<(), JsError> {
         ("app-2", "lo"), //
     ])?;
 
-    let message = tauri_wasm::invoke("headers").with_options(opts).await?;
+    let message = tauri_wasm::invoke("headers")
+        .with_options(opts)
+        .text()
+        .await?;
     console::log!("message from backend received", &message);
 
     if message == "fi.hi.lo" {
@@ -40,14 +68,239 @@ async fn headers() -> Result<(), JsError> {
     }
 }
 
-async fn event() -> Result<(), JsError> {
-    use tauri_wasm::event::EventTarget;
+async fn headers_shape() -> Result<(), JsError> {
+    use {
+        std::collections::HashMap,
+        tauri_wasm::invoke::{IntoHeaders, Options},
+        web_sys::Headers,
+    };
 
-    let target = EventTarget::from("app");
-    tauri_wasm::emit("test-event", "payload")?
-        .to(target)
+    let map = HashMap::from([
+        ("app-0", "fi"), //
+        ("app-1", "hi"), //
+        ("app-2", "lo"), //
+    ]);
+
+    let js = Headers::new().map_err(|_| JsError::new("failed to create headers"))?;
+    for (key, val) in &map {
+        js.set(key, val)
+            .map_err(|_| JsError::new("failed to set header"))?;
+    }
+
+    let all = [
+        ("map", Options::from_map(&map)?),
+        ("headers", js.into_options()?),
+        ("pairs", Options::from_pairs(map)?),
+    ];
+
+    for (shape, opts) in all {
+        let message = tauri_wasm::invoke("headers")
+            .with_options(opts)
+            .text()
+            .await?;
+        if message != "fi.hi.lo" {
+            let e = format!("wrong backend message for {shape} headers");
+            return Err(JsError::new(&e));
+        }
+    }
+
+    Ok(())
+}
+
+fn options_get() -> Result<(), JsError> {
+    use tauri_wasm::invoke::{Options, ResponseType};
+
+    let opts = Options::from_record([("secret", "2")])?.with_response_type(ResponseType::Raw);
+    if opts.get("secret").as_deref() != Some("2") {
+        return Err(JsError::new("the header should exist"));
+    }
+
+    if opts.get("data").is_some() {
+        return Err(JsError::new("the header should not exist"));
+    }
+
+    if opts.len() != 1 {
+        return Err(JsError::new("options should have one header"));
+    }
+
+    if opts.response_type().as_deref() != Some("raw") {
+        return Err(JsError::new("wrong response type of options"));
+    }
+
+    Ok(())
+}
+
+async fn bytes() -> Result<(), JsError> {
+    let data = vec![0, 1, 2, 3];
+    let len = tauri_wasm::invoke("bytes").with_args(data).await?;
+    console::log!("message from backend received", &len);
+
+    if len == 4 {
+        Ok(())
+    } else {
+        Err(JsError::new("wrong backend message"))
+    }
+}
+
+async fn unit() -> Result<(), JsError> {
+    tauri_wasm::invoke("unit").deserialize::<()>().await?;
+
+    let none: Option<u32> = tauri_wasm::invoke("unit").deserialize().await?;
+    if none.is_some() {
+        return Err(JsError::new("unit result should be none"));
+    }
+
+    tauri_wasm::from_value::<()>(&JsValue::UNDEFINED)?;
+    let none: Option<u32> = tauri_wasm::from_value(&JsValue::NULL)?;
+    if none.is_some() {
+        return Err(JsError::new("null should be none"));
+    }
+
+    Ok(())
+}
+
+async fn map_args() -> Result<(), JsError> {
+    use std::collections::HashMap;
+
+    let values = HashMap::from([("a", 1), ("b", 2), ("c", 3)]);
+    let args = tauri_wasm::args(&HashMap::from([("values", values)]))?;
+    let sum: u32 = tauri_wasm::invoke("sum")
+        .with_args(args)
+        .deserialize()
         .await?;
 
+    if sum == 6 {
+        Ok(())
+    } else {
+        Err(JsError::new("wrong sum of map values"))
+    }
+}
+
+async fn error_message() -> Result<(), JsError> {
+    // the backend rejects with a plain string
+    let Err(e) = tauri_wasm::invoke("unknown").await else {
+        return Err(JsError::new("the unknown command should fail"));
+    };
+
+    console::log!("error from backend received", e.message());
+    if !e.is_command_not_found() || e.message() != "command unknown not found" {
+        return Err(JsError::new("wrong backend error message"));
+    }
+
+    // a user error that mentions "not allowed" isn't an ACL rejection
+    let Err(e) = tauri_wasm::invoke("check_user").await else {
+        return Err(JsError::new("the user check should fail"));
+    };
+
+    if e.is_permission_denied() {
+        return Err(JsError::new("the user error is not a permission error"));
+    }
+
+    // the result check throws a JS error object
+    let Err(e) = tauri_wasm::invoke("bytes").with_args(vec![0]).bytes().await else {
+        return Err(JsError::new("the non-binary result should fail"));
+    };
+
+    if e.message() == "the invoke result is not binary" {
+        Ok(())
+    } else {
+        Err(JsError::new("wrong js error message"))
+    }
+}
+
+async fn event() -> Result<(), JsError> {
+    // a string is converted into the `AnyLabel` target
+    let target = EventTarget::from("app");
+    if !matches!(&target, EventTarget::AnyLabel(label) if label == "app") {
+        return Err(JsError::new("a string should be converted into any label"));
+    }
+
+    // a string target matches any kind with the label,
+    // so the app webview window should receive both events
+    emit_to(target, "any-label").await?;
+    emit_to(EventTarget::webview_window("app"), "webview-window").await?;
+    Ok(())
+}
+
+async fn emit_to<S>(target: EventTarget<S>, payload: &str) -> Result<(), JsError>
+where
+    S: ToStringValue,
+{
+    tauri_wasm::emit("test-event", payload)?.to(target).await?;
+
+    let last = tauri_wasm::invoke("last_event").text().await?;
+    if last == format!("\"{payload}\"") {
+        Ok(())
+    } else {
+        let e = format!("the {payload} event wasn't received by the backend");
+        Err(JsError::new(&e))
+    }
+}
+
+/// Compares building headers with `Options::from_record` with the
+/// former serde struct based path, call it from the devtools console.
+#[wasm_bindgen]
+pub fn bench_headers() -> Result<(), JsError> {
+    use {
+        serde::ser::{SerializeStruct, Serializer as _},
+        serde_wasm_bindgen::Serializer,
+        tauri_wasm::invoke::Options,
+    };
+
+    const ROUNDS: u32 = 1000;
+
+    thread_local! {
+        // the serde path caches static keys, so leak them once
+        static KEYS: Vec<&'static str> = (0..100)
+            .map(|n| -> &'static str { format!("app-{n}").leak() })
+            .collect();
+    }
+
+    let performance = web_sys::window()
+        .and_then(|w| w.performance())
+        .ok_or_else(|| JsError::new("performance is not available"))?;
+
+    let measure = |f: &dyn Fn() -> Result<(), JsError>| {
+        let start = performance.now();
+        for _ in 0..ROUNDS {
+            f()?;
+        }
+
+        let us = (performance.now() - start) * 1000. / f64::from(ROUNDS);
+        Ok::<_, JsError>(us)
+    };
+
+    for n in [1, 10, 100] {
+        let fields: Vec<(&'static str, String)> = KEYS.with(|keys| {
+            keys.iter()
+                .take(n)
+                .enumerate()
+                .map(|(n, &key)| (key, n.to_string()))
+                .collect()
+        });
+
+        let direct = measure(&|| {
+            let fields = fields.iter().map(|(key, val)| (*key, val.as_str()));
+            Options::from_record(fields)?;
+            Ok(())
+        })?;
+
+        let serde = measure(&|| {
+            let ser = Serializer::new();
+            let mut s = ser.serialize_struct("Record", fields.len())?;
+            for (key, val) in &fields {
+                s.serialize_field(key, val)?;
+            }
+
+            s.end()?;
+            Ok(())
+        })?;
+
+        console::log!(format!(
+            "{n} headers: direct {direct:.2} us, serde {serde:.2} us"
+        ));
+    }
+
     Ok(())
 }
 
@@ -72,11 +325,46 @@ async fn start() {
         return;
     }
 
+    if let Err(e) = response().await {
+        console::error!("failed to check responses", e);
+        return;
+    }
+
     if let Err(e) = headers().await {
         console::error!("failed to call headers", e);
         return;
     }
 
+    if let Err(e) = headers_shape().await {
+        console::error!("failed to check headers shape", e);
+        return;
+    }
+
+    if let Err(e) = options_get() {
+        console::error!("failed to read options", e);
+        return;
+    }
+
+    if let Err(e) = bytes().await {
+        console::error!("failed to call bytes", e);
+        return;
+    }
+
+    if let Err(e) = unit().await {
+        console::error!("failed to call unit", e);
+        return;
+    }
+
+    if let Err(e) = map_args().await {
+        console::error!("failed to call sum", e);
+        return;
+    }
+
+    if let Err(e) = error_message().await {
+        console::error!("failed to check error messages", e);
+        return;
+    }
+
     if let Err(e) = event().await {
         console::error!("failed to call event", e);
     }
@@ -2,6 +2,54 @@ export function is_tauri() {
     return 'isTauri' in window && !!window.isTauri;
 }
 
+// the global API is absent when `withGlobalTauri` is disabled,
+// but the internals are always injected by tauri
+const core = () => window.__TAURI__?.core ?? window.__TAURI_INTERNALS__;
+
+let custom = null;
+
+export function set_invoke(f) {
+    custom = f ?? null;
+}
+
+export function has_invoke() {
+    return custom !== null;
+}
+
+// errors are thrown into the promise, so
+// the caller receives them as a rejection
+const call = (cmd, args, options) => new Promise(r => r(custom
+    ? custom(cmd, args, options)
+    : core().invoke(cmd, args, options)));
+
+// the options class exposes its fields through prototype getters,
+// which are lost when tauri spreads or serializes the options,
+// so copy them into a plain object
+export function invoke(cmd, args, opts) {
+    if (!opts) return call(cmd, args);
+    const options = { headers: opts.headers };
+    const responseType = opts.responseType;
+    if (responseType) options.responseType = responseType;
+    opts.free();
+    return call(cmd, args, options);
+}
+
+export function convert_file_src(path, protocol) {
+    return core().convertFileSrc(path, protocol);
+}
+
+export function transform_callback(f, once) {
+    return core().transformCallback(f, once);
+}
+
+// older tauri versions don't provide `unregisterCallback`,
+// so remove the registered property directly
+const unregister_callback = id => {
+    const internals = window.__TAURI_INTERNALS__;
+    if (typeof internals?.unregisterCallback === 'function') internals.unregisterCallback(id);
+    else delete window[`_${id}`];
+};
+
 const ek = ['', 'Any', 'AnyLabel', 'App', 'Window', 'Webview', 'WebviewWindow'];
 
 export function eargs(event, payload, k, l) {
@@ -13,3 +61,134 @@ export function eargs(event, payload, k, l) {
 
     return o;
 }
+
+export function hget(h, k) {
+    if (!h) return undefined;
+    if (typeof h.get === 'function') return h.get(k) ?? undefined;
+    return h[k];
+}
+
+export function hlen(h) {
+    if (!h) return 0;
+    if (typeof h.keys === 'function') return Array.from(h.keys()).length;
+    return Object.keys(h).length;
+}
+
+// headers are packed into a single string of `key\0value\0` pairs,
+// so the object is built with one call
+export function unpack(s) {
+    const h = {}, parts = s.split('\0');
+    for (let i = 0; i + 1 < parts.length; i += 2) h[parts[i]] = parts[i + 1];
+    return h;
+}
+
+export function record(h) {
+    return Object.fromEntries(h.entries());
+}
+
+// the callback is unregistered when it's closed or
+// when all messages before the `end` message are delivered
+export function ordered(f) {
+    let next = 0, end = -1, pending = {};
+    const cb = m => {
+        if (!f) return;
+        // tauri before 2.5 passes the message index as `id`
+        const i = m.index ?? m.id;
+        if ('end' in m) end = i;
+        else pending[i] = m.message;
+        while (next in pending) {
+            const message = pending[next];
+            delete pending[next++];
+            f(message);
+        }
+
+        if (next === end) cb.close();
+    };
+
+    cb.id = transform_callback(cb, false);
+    cb.close = () => {
+        if (!f) return;
+        f = null;
+        pending = {};
+        unregister_callback(cb.id);
+    };
+
+    return cb;
+}
+
+export function close(cb) {
+    cb.close();
+}
+
+export function timer(ms) {
+    let id;
+    const p = new Promise(r => { id = setTimeout(r, ms); });
+    p.close = () => clearTimeout(id);
+    return p;
+}
+
+export function fargs(method, url, h, data) {
+    let headers = [];
+    if (h) headers = typeof h.entries === 'function' ? Array.from(h.entries()) : Object.entries(h);
+    return { clientConfig: { method, url, headers, data: data ? Array.from(data) : null } };
+}
+
+export function rargs(rid) {
+    return { rid };
+}
+
+export function cargs(text) {
+    return { text };
+}
+
+export function pargs(code) {
+    return { code };
+}
+
+export function current_label() {
+    return window.__TAURI_INTERNALS__?.metadata?.currentWindow?.label;
+}
+
+export function wargs(label, value) {
+    return value === undefined ? { label } : { label, value };
+}
+
+export function now() {
+    return performance.now();
+}
+
+// the event plugin internals are absent outside of tauri
+// and when the invoke function is mocked or replaced
+export function unregister_listener(event, eventId) {
+    window.__TAURI_EVENT_PLUGIN_INTERNALS__?.unregisterListener(event, eventId);
+}
+
+export function largs(event, handler, k, l) {
+    let o = { event, handler, target: { kind: ek[k] } };
+    if (l) o.target.label = l;
+    return o;
+}
+
+export function uargs(event, eventId) {
+    return { event, eventId };
+}
+
+export function aborted(s) {
+    return new Promise(r => s.aborted ? r() : s.addEventListener('abort', () => r(), { once: true }));
+}
+
+let callbacks = 0;
+
+export function mock(f) {
+    window.isTauri = true;
+    const internals = (window.__TAURI_INTERNALS__ ??= {});
+    internals.invoke = async (cmd, args) => f(cmd, args);
+    internals.transformCallback ??= (cb, once) => {
+        const id = ++callbacks, prop = `_${id}`;
+        window[prop] = m => {
+            if (once) delete window[prop];
+            return cb?.(m);
+        };
+        return id;
+    };
+}
@@ -0,0 +1,53 @@
+use crate::{error::Error, ext, invoke::api::invoke};
+
+/// Returns the tauri version of the host.
+///
+/// Returns `None` if tauri environment is not detected.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use gloo::console;
+///
+/// if let Some(version) = tauri_wasm::core_version().await? {
+///     console::log!("tauri version", version);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Capabilities
+///
+/// This function is built on the app plugin, so the
+/// `core:app:default` permission should be enabled.
+/// The same applies to [`app_name`] and [`app_version`].
+#[inline]
+pub async fn core_version() -> Result<Option<String>, Error> {
+    get("plugin:app|tauri_version").await
+}
+
+/// Returns the application name.
+///
+/// Returns `None` if tauri environment is not detected.
+#[inline]
+pub async fn app_name() -> Result<Option<String>, Error> {
+    get("plugin:app|name").await
+}
+
+/// Returns the application version.
+///
+/// Returns `None` if tauri environment is not detected.
+#[inline]
+pub async fn app_version() -> Result<Option<String>, Error> {
+    get("plugin:app|version").await
+}
+
+async fn get(cmd: &str) -> Result<Option<String>, Error> {
+    if !ext::is_tauri() {
+        return Ok(None);
+    }
+
+    let value = invoke(cmd).await?;
+    Ok(value.as_string())
+}
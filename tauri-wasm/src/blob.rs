@@ -0,0 +1,36 @@
+use {
+    crate::error::Error, js_sys::ArrayBuffer, wasm_bindgen::JsCast, wasm_bindgen_futures::JsFuture,
+    web_sys::Blob,
+};
+
+/// Reads the blob contents to pass them as invoke arguments.
+///
+/// The tauri IPC sends only binary arrays as a raw request body,
+/// other objects are serialized as JSON. A [`Blob`] has no JSON
+/// representation, so passing it as arguments directly would
+/// send an empty object. Instead, read the blob into an
+/// [`ArrayBuffer`] and pass the buffer, then the command
+/// receives the contents as a raw [request body].
+///
+/// [request body]: https://docs.rs/tauri/latest/tauri/ipc/enum.InvokeBody.html
+///
+/// The same applies to `FormData`, which isn't supported by the
+/// IPC either. Send its entries as an object or read its files
+/// as blobs instead.
+///
+/// # Example
+///
+/// ```
+/// # async fn e(file: web_sys::Blob) -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::invoke;
+///
+/// let data = invoke::read_blob(&file).await?;
+/// tauri_wasm::invoke("upload").with_args(data).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn read_blob(blob: &Blob) -> Result<ArrayBuffer, Error> {
+    let buf = JsFuture::from(blob.array_buffer()).await?;
+    Ok(buf.unchecked_into())
+}
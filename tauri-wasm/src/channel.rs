@@ -0,0 +1,274 @@
+use {
+    crate::{error::Error, ext},
+    std::{cell::RefCell, collections::VecDeque, fmt, rc::Rc},
+    wasm_bindgen::prelude::*,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, de::DeserializeOwned};
+
+/// An IPC channel to stream data from the backend.
+///
+/// A [channel] is passed to a command as an argument,
+/// then the backend can send messages through it
+/// while the command is running. This is useful to
+/// report a progress of long-running commands.
+///
+/// [channel]: https://v2.tauri.app/develop/calling-frontend/#channels
+///
+/// Messages are delivered in the order they were sent to
+/// the [`on_message`](Channel::on_message) handler. Messages
+/// received before the handler is set are kept and delivered
+/// once the handler is set. When the channel is dropped or
+/// the backend drops its end, the callback is unregistered
+/// and all further messages are ignored.
+///
+/// # Example
+///
+/// Assume the backend command takes a channel argument:
+///
+/// ```ignore
+/// #[tauri::command]
+/// fn download(on_progress: tauri::ipc::Channel<u32>) {
+///     for percent in 0..=100 {
+///         _ = on_progress.send(percent);
+///     }
+/// }
+/// ```
+///
+/// Then you can pass a channel to the command:
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, serde::Serialize, tauri_wasm::invoke::Channel};
+///
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Args<'ch> {
+///     on_progress: &'ch Channel<u32>,
+/// }
+///
+/// let on_progress = Channel::typed();
+/// on_progress.on_message(|percent| console::log!("downloaded", percent));
+///
+/// let args = tauri_wasm::args(&Args {
+///     on_progress: &on_progress,
+/// })?;
+///
+/// tauri_wasm::invoke("download").with_args(args).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Channel<T = JsValue> {
+    id: u32,
+    callback: ext::Ordered,
+    shared: Rc<RefCell<Shared<T>>>,
+    _handler: Closure<dyn FnMut(JsValue)>,
+}
+
+impl Channel {
+    /// Creates a new channel with raw JS messages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tauri callback API is not available,
+    /// for example, outside of tauri.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_decode(Ok).expect("failed to create a channel")
+    }
+}
+
+impl Default for Channel {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Channel<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    /// Creates a new channel with typed messages.
+    ///
+    /// Each message is deserialized into `T`. If deserialization
+    /// fails, the error is passed to the
+    /// [`on_error`](Channel::on_error) handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tauri callback API is not available,
+    /// for example, outside of tauri.
+    #[inline]
+    pub fn typed() -> Self {
+        Self::with_decode(decode_typed).expect("failed to create a channel")
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn decode_typed<T>(value: JsValue) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    serde_wasm_bindgen::from_value(value).map_err(Error::from)
+}
+
+impl<T> Channel<T>
+where
+    T: 'static,
+{
+    /// Creates a new channel or returns an error
+    /// if the tauri callback API is not available.
+    pub(crate) fn with_decode(decode: fn(JsValue) -> Result<T, Error>) -> Result<Self, Error> {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let handler = Closure::new({
+            let shared = shared.clone();
+            move |value| {
+                shared.borrow_mut().queue.push_back(decode(value));
+                flush(&shared);
+            }
+        });
+
+        let callback = ext::ordered(handler.as_ref()).map_err(Error::js)?;
+        let id = callback.id();
+
+        Ok(Self {
+            id,
+            callback,
+            shared,
+            _handler: handler,
+        })
+    }
+
+    /// Sets the message handler.
+    ///
+    /// The handler replaces the previous one.
+    #[inline]
+    pub fn on_message<F>(&self, f: F)
+    where
+        F: FnMut(T) + 'static,
+    {
+        self.shared.borrow_mut().on_message = Some(Box::new(f));
+        flush(&self.shared);
+    }
+
+    /// Sets the error handler.
+    ///
+    /// The handler is called when a message
+    /// cannot be decoded. If the handler is not set,
+    /// such messages are ignored.
+    #[inline]
+    pub fn on_error<F>(&self, f: F)
+    where
+        F: FnMut(Error) + 'static,
+    {
+        self.shared.borrow_mut().on_error = Some(Box::new(f));
+        flush(&self.shared);
+    }
+}
+
+impl<T> Channel<T> {
+    /// Returns the channel id.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the channel representation for invoke arguments.
+    ///
+    /// This is a JS string the backend decodes as a channel.
+    /// It's useful when arguments are built manually,
+    /// for example, with the [`Reflect`](js_sys::Reflect) API.
+    #[inline]
+    pub fn to_value(&self) -> JsValue {
+        JsValue::from(self.ipc_string())
+    }
+
+    fn ipc_string(&self) -> String {
+        format!("__CHANNEL__:{}", self.id)
+    }
+}
+
+impl<T> fmt::Debug for Channel<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Channel")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for Channel<T> {
+    #[inline]
+    fn drop(&mut self) {
+        ext::close(&self.callback);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for Channel<T> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.ipc_string())
+    }
+}
+
+type Handler<T> = Box<dyn FnMut(T)>;
+
+struct Shared<T> {
+    queue: VecDeque<Result<T, Error>>,
+    on_message: Option<Handler<T>>,
+    on_error: Option<Handler<Error>>,
+}
+
+impl<T> Default for Shared<T> {
+    fn default() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            on_message: None,
+            on_error: None,
+        }
+    }
+}
+
+fn flush<T>(shared: &RefCell<Shared<T>>) {
+    loop {
+        let mut s = shared.borrow_mut();
+        match s.queue.front() {
+            None => return,
+            Some(Ok(_)) => {
+                // take the handler out, so it can set a new handler
+                let Some(mut f) = s.on_message.take() else {
+                    return;
+                };
+
+                let Some(Ok(message)) = s.queue.pop_front() else {
+                    unreachable!();
+                };
+
+                drop(s);
+                f(message);
+                shared.borrow_mut().on_message.get_or_insert(f);
+            }
+            Some(Err(_)) => {
+                let Some(Err(e)) = s.queue.pop_front() else {
+                    unreachable!();
+                };
+
+                let Some(mut f) = s.on_error.take() else {
+                    continue;
+                };
+
+                drop(s);
+                f(e);
+                shared.borrow_mut().on_error.get_or_insert(f);
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Bindings of the [clipboard plugin].
+//!
+//! [clipboard plugin]: https://v2.tauri.app/plugin/clipboard/
+//!
+//! # Capabilities
+//!
+//! The plugin should be installed on the backend and
+//! clipboard access should be allowed explicitly, since
+//! the default permission set of the plugin is empty.
+//! For example, your `Tauri.toml` config should include
+//! something like:
+//!
+//! ```toml
+//! [[app.security.capabilities]]
+//! identifier = "default"
+//! windows = ["app"]
+//! permissions = [
+//!     "clipboard-manager:allow-read-text",
+//!     "clipboard-manager:allow-write-text",
+//! ]
+//! ```
+
+use {
+    crate::{error::Error, ext, invoke::api::invoke},
+    js_sys::TypeError,
+    wasm_bindgen::JsValue,
+};
+
+/// Writes the text to the clipboard.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::clipboard;
+///
+/// clipboard::write_text("copied").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn write_text(text: &str) -> Result<(), Error> {
+    invoke("plugin:clipboard-manager|write_text")
+        .with_args(ext::cargs(text))
+        .await?;
+
+    Ok(())
+}
+
+/// Reads the text from the clipboard.
+///
+/// Returns an [error](Error) if the
+/// clipboard doesn't contain a text.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::clipboard};
+///
+/// let text = clipboard::read_text().await?;
+/// console::log!("pasted", text);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A non-text response is an error, it isn't
+/// replaced with an empty string:
+///
+#[cfg_attr(feature = "mock", doc = "```")]
+#[cfg_attr(not(feature = "mock"), doc = "```ignore")]
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {
+///     tauri_wasm::{clipboard, mock},
+///     wasm_bindgen::JsValue,
+/// };
+///
+/// mock::on("plugin:clipboard-manager|read_text", |_| Ok(JsValue::NULL));
+/// assert!(clipboard::read_text().await.is_err());
+///
+/// mock::on("plugin:clipboard-manager|read_text", |_| Ok(JsValue::from("")));
+/// assert_eq!(clipboard::read_text().await?, "");
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn read_text() -> Result<String, Error> {
+    let text = invoke("plugin:clipboard-manager|read_text").await?;
+    text.as_string().ok_or_else(|| {
+        let e = TypeError::new("the clipboard doesn't contain a text");
+        Error::js(JsValue::from(e))
+    })
+}
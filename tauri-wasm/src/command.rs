@@ -0,0 +1,88 @@
+/// Defines typed wrappers for backend [commands].
+///
+/// [commands]: https://v2.tauri.app/develop/calling-rust/#commands
+///
+/// Each definition generates an `async fn` that serializes
+/// the arguments with [`args`](crate::args), invokes the command
+/// and deserializes the result. The syntax is:
+///
+/// ```text
+/// command! {
+///     #[attributes]
+///     VISIBILITY fn NAME(ARG: ARG_TYPE) -> RESULT_TYPE = "COMMAND";
+/// }
+/// ```
+///
+/// The argument is optional, a command without arguments
+/// is written as `fn NAME() -> RESULT_TYPE`. The argument type
+/// must implement `Serialize` and its fields are passed to the
+/// backend as command arguments. The `= "COMMAND"` part is optional
+/// too, by default the command name is the function name.
+///
+/// The generated function has the signature:
+///
+/// ```text
+/// VISIBILITY async fn NAME(ARG: ARG_TYPE) -> Result<RESULT_TYPE, tauri_wasm::Error>
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize)]
+/// struct Login<'str> {
+///     name: &'str str,
+///     pass: &'str str,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Session {
+///     token: String,
+/// }
+///
+/// tauri_wasm::command! {
+///     /// Logs in on the backend.
+///     pub fn login(login: &Login<'_>) -> Session;
+///
+///     /// Logs out on the backend.
+///     pub fn logout() -> () = "sign_out";
+/// }
+///
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// let session = login(&Login {
+///     name: "anon",
+///     pass: "p@$$w0rD",
+/// })
+/// .await?;
+///
+/// logout().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! command {
+    (@name $name:ident) => {
+        ::core::stringify!($name)
+    };
+    (@name $name:ident $cmd:literal) => {
+        $cmd
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis fn $name:ident($($arg:ident: $ty:ty)?) -> $res:ty $(= $cmd:literal)?;
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        $vis async fn $name($($arg: $ty)?) -> ::core::result::Result<$res, $crate::Error> {
+            let cmd = $crate::command!(@name $name $($cmd)?);
+            $crate::invoke(cmd)
+                $(.with_args($crate::args(&$arg)?))?
+                .deserialize()
+                .await
+        }
+
+        $crate::command! { $($rest)* }
+    };
+    () => {};
+}
@@ -1,25 +1,439 @@
 use {
     crate::ext,
     std::{error, fmt},
-    wasm_bindgen::JsValue,
+    wasm_bindgen::{JsCast, JsValue},
 };
 
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
 /// Common error type.
-#[derive(Debug)]
-pub struct Error(pub(crate) JsValue);
+///
+/// The error holds a JS value, which is usually
+/// the value a JS promise was rejected with.
+/// Use the [`kind`](Error::kind) function
+/// to find out where the error came from.
+///
+/// A JS value obtained from other JS interop can be
+/// converted into the error with the [`From`] trait, so
+/// results of `js_sys` and `web_sys` calls can be propagated with `?`
+/// alongside tauri calls. The kind is [`ErrorKind::Js`]
+/// for JS `Error` objects and [`ErrorKind::Backend`]
+/// for any other value.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// let args = js_sys::JSON::parse(r#"{"name":"anon"}"#)?;
+/// tauri_wasm::invoke("login").with_args(args).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Exported functions
+///
+/// The error implements [`std::error::Error`], so it's
+/// converted into a [`JsError`] with `?` in functions
+/// exported with `#[wasm_bindgen]`. Note that the
+/// [`JsError`] keeps only the [displayed](fmt::Display)
+/// message. To pass the original JS value to the caller,
+/// return a `Result<_, JsValue>` instead.
+///
+/// [`JsError`]: wasm_bindgen::JsError
+///
+/// ```
+/// use wasm_bindgen::prelude::*;
+///
+/// #[wasm_bindgen]
+/// pub async fn login() -> Result<(), JsError> {
+///     tauri_wasm::invoke("login").await?;
+///     Ok(())
+/// }
+///
+/// #[wasm_bindgen]
+/// pub async fn logout() -> Result<(), JsValue> {
+///     tauri_wasm::invoke("logout").await?;
+///     Ok(())
+/// }
+/// ```
+///
+/// The error can be cloned cheaply. Cloning copies
+/// the handle to the inner JS value, so the clone
+/// refers to the same JS object as the original.
+#[derive(Clone, Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    value: JsValue,
+}
+
+impl Error {
+    pub(crate) fn js(value: JsValue) -> Self {
+        let kind = if value.is_instance_of::<js_sys::Error>() {
+            ErrorKind::Js
+        } else {
+            ErrorKind::Backend
+        };
+
+        Self { kind, value }
+    }
+
+    pub(crate) fn not_tauri() -> Self {
+        let kind = ErrorKind::NotTauri;
+        let value = JsValue::from(js_sys::Error::new("not running under tauri"));
+        Self { kind, value }
+    }
+
+    pub(crate) fn timeout(ms: f64) -> Self {
+        let kind = ErrorKind::Timeout;
+        let message = format!("the operation timed out after {ms} ms");
+        let value = JsValue::from(js_sys::Error::new(&message));
+        Self { kind, value }
+    }
+
+    #[cfg(feature = "abort")]
+    pub(crate) fn cancelled(reason: JsValue) -> Self {
+        let kind = ErrorKind::Cancelled;
+        let value = reason;
+        Self { kind, value }
+    }
+
+    /// Returns the error kind.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, tauri_wasm::ErrorKind};
+    ///
+    /// match tauri_wasm::invoke("login").await {
+    ///     Ok(message) => console::log!("logged on backend", message),
+    ///     Err(e) if e.kind() == ErrorKind::Backend => {
+    ///         console::error!("the command failed", e.as_value());
+    ///     }
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the inner JS value.
+    #[inline]
+    pub fn as_value(&self) -> &JsValue {
+        &self.value
+    }
+
+    /// Returns the owned inner JS value.
+    #[inline]
+    pub fn into_value(self) -> JsValue {
+        self.value
+    }
+
+    /// Deserializes the inner JS value into a typed error.
+    ///
+    /// This is useful when a backend command returns
+    /// an error that implements `Serialize`. The command's
+    /// `Err` value is serialized and the promise is rejected
+    /// with it, so it can be recovered on the frontend.
+    ///
+    /// Note that a rejected promise value differs from
+    /// a thrown JS exception. A thrown exception is
+    /// a JS `Error` object with [`ErrorKind::Js`] kind.
+    /// It usually cannot be deserialized into the backend
+    /// error type, so check the [kind](Error::kind) first.
+    ///
+    /// Returns an [error](Error) if the value cannot be
+    /// deserialized into `E`, for example, when the value
+    /// is a plain string. In this case you can fall back
+    /// to the original error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, serde::Deserialize};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct LoginError {
+    ///     code: u32,
+    ///     message: String,
+    /// }
+    ///
+    /// if let Err(e) = tauri_wasm::invoke("login").await {
+    ///     match e.deserialize::<LoginError>() {
+    ///         Ok(e) => console::error!("failed to login", e.code, e.message),
+    ///         Err(_) => console::error!("failed to login", e.to_string()),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn deserialize<E>(&self) -> Result<E, Self>
+    where
+        E: DeserializeOwned,
+    {
+        serde_wasm_bindgen::from_value(self.value.clone()).map_err(Self::from)
+    }
+
+    /// Checks whether the backend returned an error.
+    ///
+    /// See [`ErrorKind::Backend`] for details.
+    #[inline]
+    pub fn is_backend(&self) -> bool {
+        self.kind == ErrorKind::Backend
+    }
+
+    /// Checks whether a JS exception was thrown.
+    ///
+    /// See [`ErrorKind::Js`] for details.
+    #[inline]
+    pub fn is_js(&self) -> bool {
+        self.kind == ErrorKind::Js
+    }
+
+    /// Checks whether the operation was cancelled
+    /// by an [abort signal](crate::invoke::Invoke::with_signal).
+    #[cfg(feature = "abort")]
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.kind == ErrorKind::Cancelled
+    }
+
+    /// Returns the error message.
+    ///
+    /// If the inner value is a JS `Error` object, this is
+    /// its `message` property without the error name.
+    /// Otherwise, this is the stringified inner value,
+    /// the same as the [`Display`](fmt::Display) output.
+    #[inline]
+    pub fn message(&self) -> String {
+        match self.value.dyn_ref::<js_sys::Error>() {
+            Some(e) => String::from(e.message()),
+            None => ext::to_string(&self.value),
+        }
+    }
+
+    /// Checks whether the invoked command
+    /// isn't registered on the backend.
+    ///
+    /// # Best-effort
+    ///
+    /// Tauri doesn't provide a dedicated error type for this case.
+    /// Instead, the promise is rejected with a message like
+    /// `command login not found`. This function matches that message,
+    /// so it may stop working if a future version of tauri changes it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// if let Err(e) = tauri_wasm::invoke("login").await {
+    ///     if e.is_command_not_found() {
+    ///         console::error!("the backend doesn't register the login command");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_command_not_found(&self) -> bool {
+        self.kind == ErrorKind::Backend
+            && self.value.as_string().is_some_and(|message| {
+                message
+                    .strip_prefix("command ")
+                    .is_some_and(|rest| rest.ends_with(" not found"))
+            })
+    }
+
+    /// Checks whether the invoked command isn't
+    /// permitted by the app [capabilities].
+    ///
+    /// [capabilities]: https://v2.tauri.app/security/capabilities/
+    ///
+    /// This happens, for example, when an event is
+    /// [emitted](crate::emit_str) but the `core:event:allow-emit`
+    /// permission isn't enabled for the window.
+    ///
+    /// # Best-effort
+    ///
+    /// Tauri doesn't provide a dedicated error type for this case.
+    /// Instead, the promise is rejected with a message like
+    /// `event.emit not allowed. Permissions associated with this
+    /// command: ...` in debug builds and `Command plugin:event|emit
+    /// not allowed by ACL` in release builds. This function matches
+    /// these messages, so other backend errors containing "not allowed"
+    /// aren't matched, but it may stop working if a future version
+    /// of tauri changes them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// if let Err(e) = tauri_wasm::emit_str("sync", "all").await {
+    ///     if e.is_permission_denied() {
+    ///         console::error!("this action isn't permitted");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_permission_denied(&self) -> bool {
+        self.kind == ErrorKind::Backend
+            && self
+                .value
+                .as_string()
+                .is_some_and(|message| is_acl_message(&message))
+    }
+
+    /// Checks whether the operation failed
+    /// because tauri environment isn't detected.
+    ///
+    /// This happens, for example, when the frontend
+    /// is opened in a plain browser.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// match tauri_wasm::invoke("connect").await {
+    ///     Ok(message) => console::log!("connected", message),
+    ///     Err(e) if e.is_not_tauri() => console::error!("open the app in tauri"),
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_not_tauri(&self) -> bool {
+        self.kind == ErrorKind::NotTauri
+    }
+
+    /// Checks whether the operation exceeded
+    /// its [timeout](crate::invoke::Invoke::with_timeout).
+    #[inline]
+    pub fn is_timeout(&self) -> bool {
+        self.kind == ErrorKind::Timeout
+    }
+}
+
+/// Checks whether the message is a rejection of the tauri access control.
+fn is_acl_message(message: &str) -> bool {
+    // release builds hide the details of the rejection
+    if message.starts_with("Command ") && message.ends_with(" not allowed by ACL") {
+        return true;
+    }
+
+    [
+        " not allowed. Permissions associated with this command: ",
+        " not allowed on window \"",
+        " not allowed on origin [",
+        " explicitly denied on origin ",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
 
 impl fmt::Display for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ext::to_string(&self.0).fmt(f)
+        ext::to_string(&self.value).fmt(f)
     }
 }
 
 impl error::Error for Error {}
 
+#[cfg(feature = "serde")]
+impl From<serde_wasm_bindgen::Error> for Error {
+    #[inline]
+    fn from(e: serde_wasm_bindgen::Error) -> Self {
+        Self::js(JsValue::from(e))
+    }
+}
+
+impl From<JsValue> for Error {
+    #[inline]
+    fn from(value: JsValue) -> Self {
+        Self::js(value)
+    }
+}
+
 impl From<Error> for JsValue {
     #[inline]
     fn from(e: Error) -> Self {
-        e.0
+        e.value
     }
 }
+
+/// The kind of an [error](Error).
+///
+/// New kinds may be added in future versions, so matching
+/// on the kind requires a wildcard arm. In most cases it's
+/// simpler to check the kind with a predicate method like
+/// [`is_backend`](Error::is_backend) or
+/// [`is_timeout`](Error::is_timeout) instead.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::ErrorKind};
+///
+/// if let Err(e) = tauri_wasm::invoke("sync").await {
+///     match e.kind() {
+///         ErrorKind::Backend => console::error!("sync failed", e.as_value()),
+///         ErrorKind::Timeout => console::warn!("sync is taking too long"),
+///         _ => return Err(e),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The backend returned an error.
+    ///
+    /// When a command returns an `Err`, tauri rejects
+    /// the promise with the serialized error value.
+    /// This kind is assigned to any rejected
+    /// value that isn't a JS `Error` object.
+    Backend,
+
+    /// A JS exception was thrown.
+    ///
+    /// This kind is assigned to JS `Error` objects,
+    /// for example, when an IPC request fails
+    /// or a value cannot be (de)serialized.
+    Js,
+
+    /// The operation requires tauri environment,
+    /// but it isn't detected.
+    ///
+    /// Invoke and emit operations check the environment
+    /// with [`is_tauri`](crate::is_tauri) before calling
+    /// the IPC. The error holds a JS `Error` object.
+    NotTauri,
+
+    /// The operation timed out.
+    ///
+    /// The error holds a JS `Error` object
+    /// with a message describing the timeout.
+    Timeout,
+
+    /// The operation was cancelled.
+    #[cfg(feature = "abort")]
+    Cancelled,
+}
@@ -3,17 +3,38 @@
 //! [event system]: https://v2.tauri.app/develop/calling-rust/#event-system
 
 use {
-    crate::{error::Error, ext, invoke::Options, string::ToStringValue},
-    js_sys::{JsString, Promise},
-    serde::Serialize,
+    crate::{
+        error::Error,
+        ext,
+        invoke::{State, Timeout},
+        string::ToStringValue,
+    },
+    js_sys::{Array, JsString, Promise, TypeError},
     std::{
+        cell::RefCell,
+        fmt,
         pin::Pin,
-        task::{Context, Poll},
+        rc::Rc,
+        task::{Context, Poll, Waker},
+        time::Duration,
     },
     wasm_bindgen::prelude::*,
     wasm_bindgen_futures::JsFuture,
 };
 
+#[cfg(feature = "serde")]
+use {
+    serde::{Serialize, de::DeserializeOwned},
+    serde_wasm_bindgen::Serializer,
+    std::marker::PhantomData,
+};
+
+#[cfg(feature = "stream")]
+use {futures_core::Stream, std::collections::VecDeque};
+
+#[cfg(feature = "tracing")]
+use crate::trace::Trace;
+
 #[rustfmt::skip]
 #[wasm_bindgen]
 extern "C" {
@@ -22,6 +43,26 @@ extern "C" {
 
      #[wasm_bindgen(thread_local_v2, static_string)]
     static EMIT_TO: JsString = "plugin:event|emit_to";
+
+    #[wasm_bindgen(thread_local_v2, static_string)]
+    static LISTEN: JsString = "plugin:event|listen";
+
+    #[wasm_bindgen(thread_local_v2, static_string)]
+    static UNLISTEN: JsString = "plugin:event|unlisten";
+}
+
+#[wasm_bindgen]
+extern "C" {
+    type RawEvent;
+
+    #[wasm_bindgen(method, getter)]
+    fn event(this: &RawEvent) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn id(this: &RawEvent) -> u32;
+
+    #[wasm_bindgen(method, getter)]
+    fn payload(this: &RawEvent) -> JsValue;
 }
 
 pub(crate) mod api {
@@ -86,6 +127,7 @@ pub(crate) mod api {
     /// windows = ["app"]
     /// permissions = ["core:event:default"]
     /// ```
+    #[cfg(feature = "serde")]
     #[inline]
     pub fn emit<E, P>(event: E, payload: &P) -> Result<Emit<E::Js>, Error>
     where
@@ -93,8 +135,7 @@ pub(crate) mod api {
         P: Serialize + ?Sized,
     {
         let event = event.to_string_value();
-        let payload =
-            serde_wasm_bindgen::to_value(&payload).map_err(|e| Error(JsValue::from(e)))?;
+        let payload = serde_wasm_bindgen::to_value(&payload)?;
         let target = None;
 
         Ok(Emit {
@@ -103,9 +144,286 @@ pub(crate) mod api {
             target,
         })
     }
+
+    /// Sends an [event] to all listeners.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// This is a shortcut for the [`emit`] function
+    /// with the [`EventTarget::Any`] target, so the event
+    /// is broadcast to every window and webview as well
+    /// as to the backend listeners.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "serde", doc = "```")]
+    #[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// tauri_wasm::emit_any("theme-changed", "dark")?.await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn emit_any<E, P>(event: E, payload: &P) -> Result<Emit<E::Js>, Error>
+    where
+        E: ToStringValue,
+        P: Serialize + ?Sized,
+    {
+        let mut emit = emit(event, payload)?;
+        emit.target = Some(EventTarget::Any);
+        Ok(emit)
+    }
+
+    /// Sends an [event] to the backend without waiting for the result.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// This is a shortcut for the [`emit`] function followed by
+    /// [`detach`](Emit::detach). It's useful for fire-and-forget
+    /// events like telemetry, where the result doesn't matter.
+    /// Errors are ignored, including a payload serialization error.
+    /// With the `tracing` feature, failed operations and
+    /// serialization errors are still recorded in the emit span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn e() {
+    /// tauri_wasm::emit_detached("button-clicked", "save");
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn emit_detached<E, P>(event: E, payload: &P)
+    where
+        E: ToStringValue,
+        P: Serialize + ?Sized,
+    {
+        let event = event.to_string_value();
+        match serde_wasm_bindgen::to_value(payload) {
+            Ok(payload) => {
+                let target = None;
+                let emit: Emit<_> = Emit {
+                    event,
+                    payload,
+                    target,
+                };
+
+                emit.detach();
+            }
+            #[cfg(feature = "tracing")]
+            Err(e) => Trace::emit(event.as_ref()).finish(&Err::<(), _>(Error::from(e))),
+            #[cfg(not(feature = "tracing"))]
+            Err(_) => {}
+        }
+    }
+
+    /// Sends an [event] with a raw JS payload to the backend.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// Unlike the `emit` function, the payload is passed
+    /// unchanged without serialization, so this function
+    /// is available without the `serde` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use wasm_bindgen::JsValue;
+    ///
+    /// let payload = JsValue::from(37);
+    /// tauri_wasm::emit_raw("counter-changed", &payload).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn emit_raw<E>(event: E, payload: &JsValue) -> Emit<E::Js>
+    where
+        E: ToStringValue,
+    {
+        let event = event.to_string_value();
+        let payload = payload.clone();
+        let target = None;
+
+        Emit {
+            event,
+            payload,
+            target,
+        }
+    }
+
+    /// Sends an [event] with a string payload to the backend.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// The string is passed as a JS string directly,
+    /// which is cheaper than serializing it with
+    /// the `emit` function. The backend receives
+    /// the same payload in both cases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// tauri_wasm::emit_str("file-selected", "/path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn emit_str<E>(event: E, payload: &str) -> Emit<E::Js>
+    where
+        E: ToStringValue,
+    {
+        emit_raw(event, &JsValue::from_str(payload))
+    }
+}
+
+/// A reusable emitter of serialized events.
+///
+/// The emitter holds a serializer configuration, so payloads
+/// of many events can be serialized the same way without
+/// setting the options for each call. It works like the
+/// [`emit`](api::emit) function with a custom serializer.
+///
+/// # Serializer options
+///
+/// All options are disabled by default, which matches
+/// the serializer used by the [`emit`](api::emit) function:
+///
+/// | Option | Default | When enabled |
+/// |--------|---------|--------------|
+/// | [`with_missing_as_null`](Emitter::with_missing_as_null) | `false` | `()` and `None` are serialized as `null` instead of `undefined` |
+/// | [`with_maps_as_objects`](Emitter::with_maps_as_objects) | `false` | maps are serialized as plain objects instead of JS `Map` |
+/// | [`with_large_numbers_as_bigints`](Emitter::with_large_numbers_as_bigints) | `false` | `i64` and `u64` are serialized as `BigInt` instead of numbers |
+/// | [`with_bytes_as_arrays`](Emitter::with_bytes_as_arrays) | `false` | bytes are serialized as arrays instead of `Uint8Array` |
+///
+/// Note that the backend receives event payloads as JSON, where
+/// a JS `Map` becomes an empty object and `undefined` fields are
+/// skipped. So to send maps to the backend, enable the
+/// [`with_maps_as_objects`](Emitter::with_maps_as_objects) option
+/// or create the emitter with [`json_compatible`](Emitter::json_compatible).
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {std::collections::HashMap, tauri_wasm::event::Emitter};
+///
+/// let emitter = Emitter::new().with_maps_as_objects(true);
+/// for n in 0..3 {
+///     let progress = HashMap::from([("step", n)]);
+///     emitter.emit("progress", &progress)?.await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Default)]
+pub struct Emitter {
+    ser: Serializer,
+}
+
+#[cfg(feature = "serde")]
+impl Emitter {
+    /// Creates an emitter with default options.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            ser: Serializer::new(),
+        }
+    }
+
+    /// Creates an emitter compatible with JSON.
+    ///
+    /// This enables the [`with_missing_as_null`](Emitter::with_missing_as_null)
+    /// and [`with_maps_as_objects`](Emitter::with_maps_as_objects) options,
+    /// so payloads are serialized the same way as with `JSON.stringify`.
+    #[inline]
+    pub const fn json_compatible() -> Self {
+        Self {
+            ser: Serializer::json_compatible(),
+        }
+    }
+
+    /// Serializes `()` and `None` as `null` instead of `undefined`.
+    #[inline]
+    pub const fn with_missing_as_null(self, yes: bool) -> Self {
+        Self {
+            ser: self.ser.serialize_missing_as_null(yes),
+        }
+    }
+
+    /// Serializes maps as plain objects instead of JS `Map`.
+    #[inline]
+    pub const fn with_maps_as_objects(self, yes: bool) -> Self {
+        Self {
+            ser: self.ser.serialize_maps_as_objects(yes),
+        }
+    }
+
+    /// Serializes `i64` and `u64` as `BigInt` instead of numbers.
+    ///
+    /// Note that `BigInt` values can't be converted to JSON,
+    /// so such payloads can't be sent to the backend.
+    #[inline]
+    pub const fn with_large_numbers_as_bigints(self, yes: bool) -> Self {
+        Self {
+            ser: self.ser.serialize_large_number_types_as_bigints(yes),
+        }
+    }
+
+    /// Serializes bytes as arrays instead of `Uint8Array`.
+    #[inline]
+    pub const fn with_bytes_as_arrays(self, yes: bool) -> Self {
+        Self {
+            ser: self.ser.serialize_bytes_as_arrays(yes),
+        }
+    }
+
+    /// Serializes the payload and sends an event to the backend.
+    ///
+    /// This is the same as the [`emit`](api::emit) function,
+    /// so the event can be targeted with the [`to`](Emit::to)
+    /// function.
+    #[inline]
+    pub fn emit<E, P>(&self, event: E, payload: &P) -> Result<Emit<E::Js>, Error>
+    where
+        E: ToStringValue,
+        P: Serialize + ?Sized,
+    {
+        let payload = payload.serialize(&self.ser)?;
+        Ok(api::emit_raw(event, &payload))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Debug for Emitter {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Emitter").finish_non_exhaustive()
+    }
 }
 
-/// A type used to configure an [emit](api::emit) operation.
+/// A type used to configure an [emit](api::emit_raw) operation.
+///
+/// # Delivery
+///
+/// The emit future resolves when the backend has dispatched the event,
+/// not when it was handled. Backend listeners are usually called before
+/// the IPC call completes, but a listener that is registered or removed
+/// concurrently may receive the event later. Frontend listeners are
+/// notified asynchronously through their webviews. Tauri doesn't report
+/// how many listeners were notified, so a successful result doesn't
+/// mean anyone received the event.
+///
+/// If the frontend must know the event was processed, for example,
+/// exactly once, invoke a [command](crate::invoke()) instead and
+/// return the acknowledgement from it.
+#[derive(Debug)]
+#[must_use = "this does nothing unless awaited"]
 pub struct Emit<E, T = JsValue> {
     event: E,
     payload: JsValue,
@@ -146,26 +464,231 @@ impl<E> Emit<E> {
             target,
         }
     }
+
+    /// Sends an [event] to listeners registered by several targets.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// The event is sent to each target with a separate
+    /// IPC call, all calls are performed concurrently.
+    /// The future resolves when all calls complete.
+    /// If any call fails, the future resolves with the error
+    /// of the first failed call, but the other calls are
+    /// still performed. If there are no targets,
+    /// nothing is sent.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "serde", doc = "```")]
+    #[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::event::EventTarget;
+    ///
+    /// let targets = ["editor", "preview"].map(EventTarget::webview);
+    /// tauri_wasm::emit("file-selected", "/path/to/file")?
+    ///     .to_many(targets)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn to_many<I, S>(self, targets: I) -> EmitMany<E, S::Js>
+    where
+        I: IntoIterator<Item = EventTarget<S>>,
+        S: ToStringValue,
+    {
+        let event = self.event;
+        let payload = self.payload;
+        let targets = targets
+            .into_iter()
+            .map(|target| target.map(|s| s.to_string_value()))
+            .collect();
+
+        EmitMany {
+            event,
+            payload,
+            targets,
+        }
+    }
+}
+
+/// A type used to configure an [emit](api::emit_raw)
+/// operation to several targets.
+#[must_use = "this does nothing unless awaited"]
+pub struct EmitMany<E, T> {
+    event: E,
+    payload: JsValue,
+    targets: Vec<EventTarget<T>>,
+}
+
+impl<E, T> IntoFuture for EmitMany<E, T>
+where
+    E: AsRef<JsValue>,
+    T: AsRef<JsValue>,
+{
+    type Output = Result<(), Error>;
+    type IntoFuture = EmitManyFuture;
+
+    #[inline]
+    fn into_future(self) -> Self::IntoFuture {
+        let state = State::start(|| {
+            let promises: Array = self
+                .targets
+                .iter()
+                .map(|target| {
+                    let target = Some(target.as_ref().map(|s| s.as_ref()));
+                    invoke_emit(target, self.event.as_ref(), &self.payload)
+                })
+                .collect();
+
+            Promise::all(&promises)
+        });
+
+        EmitManyFuture {
+            state,
+            #[cfg(feature = "tracing")]
+            trace: Trace::emit(self.event.as_ref()),
+        }
+    }
+}
+
+/// Represents the future of an [emit](api::emit_raw)
+/// operation to several targets.
+#[must_use = "this does nothing unless awaited"]
+pub struct EmitManyFuture {
+    state: State,
+    #[cfg(feature = "tracing")]
+    trace: Trace,
+}
+
+impl EmitManyFuture {
+    /// Returns the inner future.
+    #[inline]
+    pub fn into_future(self) -> JsFuture {
+        self.state.into_future()
+    }
+}
+
+impl Future for EmitManyFuture {
+    type Output = Result<(), Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+
+        #[cfg(feature = "tracing")]
+        let _enter = me.trace.span().enter();
+
+        let res = me.state.poll(cx);
+
+        #[cfg(feature = "tracing")]
+        if let Poll::Ready(res) = &res {
+            me.trace.finish(res);
+        }
+
+        res.map_ok(drop)
+    }
+}
+
+impl fmt::Debug for EmitManyFuture {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmitManyFuture").finish_non_exhaustive()
+    }
 }
 
-/// Represents the future of an [emit](api::emit) operation.
-pub struct EmitFuture(JsFuture);
+/// Represents the future of an [emit](api::emit_raw) operation.
+///
+/// # Result
+///
+/// The backend emit commands return nothing, so a successful
+/// emit resolves with `()`. Tauri doesn't assign ids
+/// to emitted events and doesn't report their listeners, so
+/// the result carries no metadata about the event. Note that
+/// the [id](Event::id) of a received event identifies the
+/// listener, not the emit call. See [delivery](Emit#delivery)
+/// for what a successful result means.
+///
+/// # Migration
+///
+/// Previously, the future resolved with a [`JsValue`], which
+/// was always `null`. Code that ignored the value with
+/// `.await?` keeps working. If the value was used, for example,
+/// returned from a function with the `Result<JsValue, Error>`
+/// type, replace it with `()`. The raw value is still
+/// available through the [`into_future`](EmitFuture::into_future)
+/// function.
+#[must_use = "this does nothing unless awaited"]
+pub struct EmitFuture {
+    state: State,
+    #[cfg(feature = "tracing")]
+    trace: Trace,
+}
 
 impl EmitFuture {
     /// Returns the inner future.
     #[inline]
     pub fn into_future(self) -> JsFuture {
-        self.0
+        self.state.into_future()
     }
 }
 
 impl Future for EmitFuture {
-    type Output = Result<JsValue, Error>;
+    type Output = Result<(), Error>;
 
     #[inline]
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let me = self.get_mut();
-        Pin::new(&mut me.0).poll(cx).map_err(Error)
+
+        #[cfg(feature = "tracing")]
+        let _enter = me.trace.span().enter();
+
+        let res = me.state.poll(cx);
+
+        #[cfg(feature = "tracing")]
+        if let Poll::Ready(res) = &res {
+            me.trace.finish(res);
+        }
+
+        res.map_ok(drop)
+    }
+}
+
+impl fmt::Debug for EmitFuture {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmitFuture").finish_non_exhaustive()
+    }
+}
+
+impl<E, T> Emit<E, T>
+where
+    E: AsRef<JsValue>,
+    T: AsRef<JsValue>,
+{
+    /// Sends the event without waiting for the result.
+    ///
+    /// The operation is started immediately and its result
+    /// is ignored, so the event is sent even if the
+    /// returned value isn't awaited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn e() {
+    /// use tauri_wasm::EventTarget;
+    ///
+    /// tauri_wasm::emit_str("button-clicked", "save")
+    ///     .to(EventTarget::from("editor"))
+    ///     .detach();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn detach(self) {
+        let fut = self.into_future();
+        wasm_bindgen_futures::spawn_local(async {
+            _ = fut.await;
+        });
     }
 }
 
@@ -174,14 +697,18 @@ where
     E: AsRef<JsValue>,
     T: AsRef<JsValue>,
 {
-    type Output = Result<JsValue, Error>;
+    type Output = Result<(), Error>;
     type IntoFuture = EmitFuture;
 
     #[inline]
     fn into_future(self) -> Self::IntoFuture {
         let target = self.target.as_ref().map(|s| s.as_ref().map(|s| s.as_ref()));
-        let promise = invoke_emit(target, self.event.as_ref(), &self.payload);
-        EmitFuture(JsFuture::from(promise))
+        let state = State::start(|| invoke_emit(target, self.event.as_ref(), &self.payload));
+        EmitFuture {
+            state,
+            #[cfg(feature = "tracing")]
+            trace: Trace::emit(self.event.as_ref()),
+        }
     }
 }
 
@@ -206,31 +733,1293 @@ fn invoke_emit(
 
     let cmd = cmd.with(|s| JsValue::from(s));
     let args = ext::eargs(event, payload, kind, label);
-    ext::invoke(&cmd, &args, Options::empty())
+    ext::invoke(&cmd, &args, None)
 }
 
-/// An argument of event target for the [`to`](Emit::to) function.
-pub enum EventTarget<S> {
-    Any,
-    AnyLabel(S),
-    App,
-    Window(S),
-    Webview(S),
-    WebviewWindow(S),
-}
+/// Listens to an [event] from the backend.
+///
+/// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+///
+/// The handler is called for every received [event](Event).
+/// This function returns a future-like object which resolves
+/// to the [`Unlisten`] handle when the listener is registered.
+/// The listener stays registered while the handle is alive.
+///
+/// By default, the listener receives events for any target.
+/// To listen to events for a specific target
+/// you can use the [`to`](Listen::to) function.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::event};
+///
+/// let unlisten = event::listen("file-selected", |ev| {
+///     console::log!("file selected", ev.payload());
+/// })
+/// .await?;
+///
+/// // stop listening
+/// unlisten.unlisten().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Capabilities
+///
+/// Like emitting, listening to events requires
+/// the `core:event:default` permission to be enabled.
+#[inline]
+pub fn listen<E, F>(event: E, handler: F) -> Listen<E::Js, F>
+where
+    E: ToStringValue,
+    F: FnMut(Event) + 'static,
+{
+    let event = event.to_string_value();
+    let target = None;
 
-impl<S> EventTarget<S> {
-    #[inline]
-    pub fn from_string(s: S) -> Self {
-        Self::AnyLabel(s)
+    Listen {
+        event,
+        handler,
+        target,
     }
+}
 
-    #[inline]
-    pub fn as_ref(&self) -> EventTarget<&S> {
-        match self {
-            Self::Any => EventTarget::Any,
-            Self::AnyLabel(s) => EventTarget::AnyLabel(s),
-            Self::App => EventTarget::App,
+/// A type used to configure a [listen] operation.
+#[must_use = "this does nothing unless awaited"]
+pub struct Listen<E, F, T = JsValue> {
+    event: E,
+    handler: F,
+    target: Option<EventTarget<T>>,
+}
+
+impl<E, F> Listen<E, F> {
+    /// Listens to an [event] for a specific target.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// # Example
+    ///
+    /// Listen to events emitted to the target with "editor" label.
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {
+    ///     gloo::console,
+    ///     tauri_wasm::event::{self, EventTarget},
+    /// };
+    ///
+    /// let target = EventTarget::from("editor");
+    /// let unlisten = event::listen("file-selected", |ev| {
+    ///     console::log!("file selected", ev.payload());
+    /// })
+    /// .to(target)
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn to<S>(self, target: EventTarget<S>) -> Listen<E, F, S::Js>
+    where
+        S: ToStringValue,
+    {
+        let event = self.event;
+        let handler = self.handler;
+        let target = Some(target.map(|s| s.to_string_value()));
+
+        Listen {
+            event,
+            handler,
+            target,
+        }
+    }
+}
+
+impl<E, F, T> IntoFuture for Listen<E, F, T>
+where
+    E: AsRef<JsValue>,
+    F: FnMut(Event) + 'static,
+    T: AsRef<JsValue>,
+{
+    type Output = Result<Unlisten, Error>;
+    type IntoFuture = ListenFuture;
+
+    #[inline]
+    fn into_future(self) -> Self::IntoFuture {
+        let mut handler = self.handler;
+        let handler = Closure::new(move |raw: RawEvent| handler(Event::from_raw(&raw)));
+
+        let event = self.event.as_ref();
+        let target = self.target.as_ref().map(|s| s.as_ref().map(|s| s.as_ref()));
+        let state = State::start(|| invoke_listen(target, event, &handler));
+
+        ListenFuture {
+            event: event.clone(),
+            state: Some((state, handler)),
+        }
+    }
+}
+
+/// Listens to an [event] from the backend
+/// and deserializes its payload.
+///
+/// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+///
+/// This is like the [`listen`] function, but the handler
+/// receives [events](Event) with the payload deserialized
+/// into `P`. If a payload cannot be deserialized, the event
+/// is skipped and the error is passed to the handler set with
+/// the [`on_error`](ListenTyped::on_error) function.
+/// Without the error handler such events are ignored.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {
+///     gloo::console,
+///     serde::Deserialize,
+///     tauri_wasm::event::{self, Event},
+/// };
+///
+/// #[derive(Deserialize)]
+/// struct Download {
+///     url: String,
+///     percent: u32,
+/// }
+///
+/// let unlisten = event::listen_typed("download", |ev: Event<Download>| {
+///     let download = ev.payload();
+///     console::log!("downloading", &download.url, download.percent);
+/// })
+/// .on_error(|e| console::error!("invalid download event", e.to_string()))
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Reentrancy
+///
+/// Handlers are called from the JS event loop between
+/// other tasks, for example while an async function waits
+/// on an `.await` point. So if a handler shares a state with
+/// other code through a `RefCell`, that code must not hold
+/// the borrow across an `.await`, otherwise the handler panics
+/// on borrowing. Note that a panic in the handler cannot be
+/// caught by the caller, so handle errors inside it.
+#[cfg(feature = "serde")]
+#[inline]
+pub fn listen_typed<P, E, F>(event: E, handler: F) -> ListenTyped<E::Js, P, F>
+where
+    E: ToStringValue,
+    F: FnMut(Event<P>) + 'static,
+{
+    let event = event.to_string_value();
+    let on_error = None;
+    let target = None;
+
+    ListenTyped {
+        event,
+        handler,
+        on_error,
+        target,
+        ty: PhantomData,
+    }
+}
+
+/// A type used to configure a [typed listen](listen_typed) operation.
+#[cfg(feature = "serde")]
+#[must_use = "this does nothing unless awaited"]
+pub struct ListenTyped<E, P, F, G = fn(Error), T = JsValue> {
+    event: E,
+    handler: F,
+    on_error: Option<G>,
+    target: Option<EventTarget<T>>,
+    ty: PhantomData<fn(P)>,
+}
+
+#[cfg(feature = "serde")]
+impl<E, P, F, G> ListenTyped<E, P, F, G> {
+    /// Listens to an [event] for a specific target.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// See [`Listen::to`] for details.
+    #[inline]
+    pub fn to<S>(self, target: EventTarget<S>) -> ListenTyped<E, P, F, G, S::Js>
+    where
+        S: ToStringValue,
+    {
+        ListenTyped {
+            event: self.event,
+            handler: self.handler,
+            on_error: self.on_error,
+            target: Some(target.map(|s| s.to_string_value())),
+            ty: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E, P, F, G, T> ListenTyped<E, P, F, G, T> {
+    /// Sets the handler of deserialization errors.
+    #[inline]
+    pub fn on_error<H>(self, on_error: H) -> ListenTyped<E, P, F, H, T>
+    where
+        H: FnMut(Error) + 'static,
+    {
+        ListenTyped {
+            event: self.event,
+            handler: self.handler,
+            on_error: Some(on_error),
+            target: self.target,
+            ty: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E, P, F, G, T> IntoFuture for ListenTyped<E, P, F, G, T>
+where
+    E: AsRef<JsValue>,
+    P: DeserializeOwned + 'static,
+    F: FnMut(Event<P>) + 'static,
+    G: FnMut(Error) + 'static,
+    T: AsRef<JsValue>,
+{
+    type Output = Result<Unlisten, Error>;
+    type IntoFuture = ListenFuture;
+
+    #[inline]
+    fn into_future(self) -> Self::IntoFuture {
+        let mut handler = self.handler;
+        let mut on_error = self.on_error;
+        let handler = move |ev: Event| match ev.deserialize() {
+            Ok(ev) => handler(ev),
+            Err(e) => {
+                if let Some(on_error) = &mut on_error {
+                    on_error(e);
+                }
+            }
+        };
+
+        let listen = Listen {
+            event: self.event,
+            handler,
+            target: self.target,
+        };
+
+        listen.into_future()
+    }
+}
+
+type Handler = Closure<dyn FnMut(RawEvent)>;
+
+/// Represents the future of a [listen] operation.
+#[must_use = "this does nothing unless awaited"]
+pub struct ListenFuture {
+    event: JsValue,
+    state: Option<(State, Handler)>,
+}
+
+impl Future for ListenFuture {
+    type Output = Result<Unlisten, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        let Some((state, _)) = &mut me.state else {
+            panic!("`ListenFuture` polled after completion");
+        };
+
+        let res = match state.poll(cx) {
+            Poll::Ready(res) => res,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let (_, handler) = me.state.take().expect("the state should exist");
+        let id = to_listener_id(&res?)?;
+        let unlisten = Unlisten {
+            listener: UnlistenId {
+                event: me.event.clone(),
+                id,
+            },
+            handler: Some(handler),
+        };
+
+        Poll::Ready(Ok(unlisten))
+    }
+}
+
+impl Drop for ListenFuture {
+    #[inline]
+    fn drop(&mut self) {
+        // the listener can still be registered after the future
+        // is dropped, so wait for it and immediately unregister
+        if let Some((state, handler)) = self.state.take() {
+            let event = self.event.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let id = state.into_future().await.ok();
+                if let Some(Ok(id)) = id.as_ref().map(to_listener_id) {
+                    _ = JsFuture::from(invoke_unlisten(&event, id)).await;
+                }
+
+                drop(handler);
+            });
+        }
+    }
+}
+
+/// Converts a listener id returned by the backend.
+///
+/// A malformed id isn't replaced with a default one,
+/// so dropping the handle never unregisters another listener.
+fn to_listener_id(value: &JsValue) -> Result<u32, Error> {
+    match value.as_f64() {
+        Some(id) if id >= 0. && id <= f64::from(u32::MAX) && id.fract() == 0. => Ok(id as u32),
+        _ => {
+            let e = TypeError::new("the event listener id is not a valid number");
+            Err(Error::js(JsValue::from(e)))
+        }
+    }
+}
+
+/// A handle of a registered [listener](listen).
+///
+/// When the handle is dropped, the listener is unregistered.
+/// To wait until the backend unregisters the listener,
+/// use the [`unlisten`](Unlisten::unlisten) function.
+#[must_use = "the listener is removed when this is dropped"]
+pub struct Unlisten {
+    listener: UnlistenId,
+    handler: Option<Handler>,
+}
+
+impl Unlisten {
+    /// Returns the event listener id.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.listener.id
+    }
+
+    /// Unregisters the event listener.
+    #[inline]
+    pub fn unlisten(mut self) -> UnlistenFuture {
+        self.handler = None;
+        self.listener.clone().unlisten()
+    }
+
+    /// Converts the handle into the raw listener id.
+    ///
+    /// After this call, the listener is no longer unregistered
+    /// automatically, so you have to do it manually with
+    /// the [`UnlistenId::unlisten`] function. Note that the
+    /// handler is leaked to stay callable from JS, even after
+    /// the listener is unregistered.
+    #[inline]
+    pub fn into_id(mut self) -> UnlistenId {
+        if let Some(handler) = self.handler.take() {
+            handler.forget();
+        }
+
+        self.listener.clone()
+    }
+}
+
+/// A raw id of a registered [listener](listen).
+///
+/// Unlike [`Unlisten`], this is a plain id that
+/// doesn't unregister the listener when dropped.
+/// It's useful when the listener lifetime
+/// is managed manually.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::event};
+///
+/// let id = event::listen("file-selected", |ev| {
+///     console::log!("file selected", ev.payload());
+/// })
+/// .await?
+/// .into_id();
+///
+/// // later, stop listening
+/// id.unlisten().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct UnlistenId {
+    event: JsValue,
+    id: u32,
+}
+
+impl UnlistenId {
+    /// Creates a listener id from the event name and the raw id.
+    ///
+    /// The id is the value a `plugin:event|listen`
+    /// command resolves with.
+    #[inline]
+    pub fn new<E>(event: E, id: u32) -> Self
+    where
+        E: ToStringValue,
+    {
+        let event = event.to_string_value().as_ref().clone();
+        Self { event, id }
+    }
+
+    /// Returns the event listener id.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Unregisters the event listener.
+    #[inline]
+    pub fn unlisten(self) -> UnlistenFuture {
+        let promise = invoke_unlisten(&self.event, self.id);
+        UnlistenFuture(JsFuture::from(promise))
+    }
+}
+
+impl Drop for Unlisten {
+    #[inline]
+    fn drop(&mut self) {
+        if self.handler.take().is_some() {
+            _ = invoke_unlisten(&self.listener.event, self.listener.id);
+        }
+    }
+}
+
+/// Represents the future of an [unlisten](Unlisten::unlisten) operation.
+#[must_use = "this does nothing unless awaited"]
+pub struct UnlistenFuture(JsFuture);
+
+impl UnlistenFuture {
+    /// Returns the inner future.
+    #[inline]
+    pub fn into_future(self) -> JsFuture {
+        self.0
+    }
+}
+
+impl Future for UnlistenFuture {
+    type Output = Result<(), Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.0).poll(cx).map_ok(drop).map_err(Error::js)
+    }
+}
+
+/// Listens to an [event] from the backend once.
+///
+/// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+///
+/// This function returns a future-like object which resolves
+/// to the first received [event](Event). After that,
+/// the listener is unregistered. The listener is also
+/// unregistered when the future is dropped before resolving.
+///
+/// To listen to an event for a specific target
+/// you can use the [`to`](Once::to) function.
+///
+/// # Example
+///
+/// Wait for a "ready" signal from the backend.
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::event};
+///
+/// let ev = event::once("ready").await?;
+/// console::log!("backend is ready", ev.payload());
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn once<E>(event: E) -> Once<E::Js>
+where
+    E: ToStringValue,
+{
+    let event = event.to_string_value();
+    let target = None;
+    let timeout = None;
+    Once {
+        event,
+        target,
+        timeout,
+    }
+}
+
+/// Listens to an [event] from the backend once
+/// with a time limit.
+///
+/// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+///
+/// This is a shortcut for the [`once`] function
+/// with the [`with_timeout`](Once::with_timeout) option.
+///
+/// # Example
+///
+/// Wait for the backend to be ready during startup.
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, std::time::Duration, tauri_wasm::event};
+///
+/// match event::once_timeout("ready", Duration::from_secs(3)).await {
+///     Ok(_) => console::log!("the backend is ready"),
+///     Err(e) if e.is_timeout() => console::error!("the backend isn't ready"),
+///     Err(e) => return Err(e),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn once_timeout<E>(event: E, timeout: Duration) -> Once<E::Js>
+where
+    E: ToStringValue,
+{
+    once(event).with_timeout(timeout)
+}
+
+/// A type used to configure a [once] operation.
+#[must_use = "this does nothing unless awaited"]
+pub struct Once<E, T = JsValue> {
+    event: E,
+    target: Option<EventTarget<T>>,
+    timeout: Option<Duration>,
+}
+
+impl<E> Once<E> {
+    /// Listens to an [event] for a specific target once.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// # Example
+    ///
+    /// Wait for a "ready" signal emitted to the target with "editor" label.
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::event::{self, EventTarget};
+    ///
+    /// let target = EventTarget::from("editor");
+    /// let ev = event::once("ready").to(target).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn to<S>(self, target: EventTarget<S>) -> Once<E, S::Js>
+    where
+        S: ToStringValue,
+    {
+        let event = self.event;
+        let target = Some(target.map(|s| s.to_string_value()));
+        let timeout = self.timeout;
+        Once {
+            event,
+            target,
+            timeout,
+        }
+    }
+}
+
+impl<E, T> Once<E, T> {
+    /// Listens to an [event] once with a time limit.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// If the event isn't received within the duration,
+    /// the listener is unregistered and the future resolves
+    /// with an [error] for which [`is_timeout`](Error::is_timeout)
+    /// returns `true`. The timer is cleared once the future
+    /// completes or is dropped.
+    ///
+    /// [error]: Error
+    #[inline]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let timeout = Some(timeout);
+        Self { timeout, ..self }
+    }
+}
+
+impl<E, T> IntoFuture for Once<E, T>
+where
+    E: AsRef<JsValue>,
+    T: AsRef<JsValue>,
+{
+    type Output = Result<Event, Error>;
+    type IntoFuture = OnceFuture;
+
+    #[inline]
+    fn into_future(self) -> Self::IntoFuture {
+        let slot = Rc::new(RefCell::new(Slot::default()));
+        let listen = Listen {
+            event: self.event,
+            handler: {
+                let slot = slot.clone();
+                move |ev| slot.borrow_mut().put(ev)
+            },
+            target: self.target,
+        };
+
+        OnceFuture {
+            slot,
+            listen: Some(listen.into_future()),
+            unlisten: None,
+            timeout: self.timeout.map(Timeout::new),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Slot {
+    event: Option<Event>,
+    waker: Option<Waker>,
+}
+
+impl Slot {
+    fn put(&mut self, ev: Event) {
+        if self.event.is_some() {
+            return;
+        }
+
+        self.event = Some(ev);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Represents the future of a [once] operation.
+#[must_use = "this does nothing unless awaited"]
+pub struct OnceFuture {
+    slot: Rc<RefCell<Slot>>,
+    listen: Option<ListenFuture>,
+    unlisten: Option<Unlisten>,
+    timeout: Option<Timeout>,
+}
+
+impl Future for OnceFuture {
+    type Output = Result<Event, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        if let Some(fut) = &mut me.listen {
+            if let Poll::Ready(res) = Pin::new(fut).poll(cx) {
+                me.listen = None;
+                me.unlisten = Some(res?);
+            }
+        }
+
+        let mut slot = me.slot.borrow_mut();
+        match slot.event.take() {
+            Some(ev) => {
+                drop(slot);
+
+                // unregister the listener and clear the timer
+                me.listen = None;
+                me.unlisten = None;
+                me.timeout = None;
+                Poll::Ready(Ok(ev))
+            }
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                drop(slot);
+
+                if let Some(timeout) = &mut me.timeout {
+                    if let Poll::Ready(e) = timeout.poll(cx) {
+                        me.listen = None;
+                        me.unlisten = None;
+                        me.timeout = None;
+                        return Poll::Ready(Err(e));
+                    }
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Listens to an [event] from the backend as a stream.
+///
+/// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+///
+/// This function returns a future-like object which resolves
+/// to the [`EventStream`] when the listener is registered.
+/// The stream yields every received [event](Event).
+/// When the stream is dropped, the listener is unregistered.
+///
+/// To listen to events for a specific target
+/// you can use the [`to`](Subscribe::to) function.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {futures_util::StreamExt, gloo::console, tauri_wasm::event};
+///
+/// let mut stream = event::stream("file-selected").await?;
+/// while let Some(ev) = stream.next().await {
+///     console::log!("file selected", ev.payload());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "stream")]
+#[inline]
+pub fn stream<E>(event: E) -> Subscribe<E::Js>
+where
+    E: ToStringValue,
+{
+    let event = event.to_string_value();
+    let target = None;
+    let bound = None;
+    Subscribe {
+        event,
+        target,
+        bound,
+    }
+}
+
+/// Listens to an [event] from the backend as a bounded stream.
+///
+/// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+///
+/// This is like the [`stream`] function, but the stream keeps
+/// at most `capacity` received events. Handlers of JS events
+/// can't wait until the stream is consumed, so when events
+/// arrive faster than the stream is polled and the stream is
+/// full, the events are dropped instead.
+///
+/// By default, the oldest event is dropped to make room for
+/// the new one, so the stream always yields the latest events.
+/// Use the [`with_overflow`](Subscribe::with_overflow) function
+/// to change the [policy](Overflow). The number of dropped events
+/// is available with the [`dropped`](EventStream::dropped) function.
+///
+/// A zero capacity is treated as one.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {futures_util::StreamExt, gloo::console, tauri_wasm::event};
+///
+/// let mut stream = event::stream_bounded("cursor-moved", 16).await?;
+/// while let Some(ev) = stream.next().await {
+///     console::log!("cursor moved", ev.payload());
+///     if stream.dropped() > 0 {
+///         console::warn!("skipped events", stream.dropped());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "stream")]
+#[inline]
+pub fn stream_bounded<E>(event: E, capacity: usize) -> Subscribe<E::Js>
+where
+    E: ToStringValue,
+{
+    let event = event.to_string_value();
+    let target = None;
+    let bound = Some(Bound {
+        capacity: capacity.max(1),
+        overflow: Overflow::DropOldest,
+    });
+
+    Subscribe {
+        event,
+        target,
+        bound,
+    }
+}
+
+/// A policy of a [bounded stream](stream_bounded)
+/// when it's full.
+#[cfg(feature = "stream")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Drops the oldest event in the stream
+    /// to make room for the new one.
+    #[default]
+    DropOldest,
+
+    /// Drops the new event and keeps
+    /// the events in the stream.
+    DropNewest,
+}
+
+#[cfg(feature = "stream")]
+#[derive(Clone, Copy)]
+struct Bound {
+    capacity: usize,
+    overflow: Overflow,
+}
+
+/// A type used to configure a [stream] operation.
+#[cfg(feature = "stream")]
+#[must_use = "this does nothing unless awaited"]
+pub struct Subscribe<E, T = JsValue> {
+    event: E,
+    target: Option<EventTarget<T>>,
+    bound: Option<Bound>,
+}
+
+#[cfg(feature = "stream")]
+impl<E> Subscribe<E> {
+    /// Listens to an [event] for a specific target as a stream.
+    ///
+    /// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+    ///
+    /// # Example
+    ///
+    /// Listen to events emitted to the target with "editor" label.
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::event::{self, EventTarget};
+    ///
+    /// let target = EventTarget::from("editor");
+    /// let stream = event::stream("file-selected").to(target).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn to<S>(self, target: EventTarget<S>) -> Subscribe<E, S::Js>
+    where
+        S: ToStringValue,
+    {
+        let event = self.event;
+        let target = Some(target.map(|s| s.to_string_value()));
+        let bound = self.bound;
+        Subscribe {
+            event,
+            target,
+            bound,
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<E, T> Subscribe<E, T> {
+    /// Sets the [policy](Overflow) of a full
+    /// [bounded stream](stream_bounded).
+    ///
+    /// It has no effect on an unbounded stream.
+    ///
+    /// # Example
+    ///
+    /// Keep the first received events and drop the new ones.
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::event::{self, Overflow};
+    ///
+    /// let stream = event::stream_bounded("log", 100)
+    ///     .with_overflow(Overflow::DropNewest)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_overflow(self, overflow: Overflow) -> Self {
+        let bound = self.bound.map(|bound| Bound { overflow, ..bound });
+        Self { bound, ..self }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<E, T> IntoFuture for Subscribe<E, T>
+where
+    E: AsRef<JsValue>,
+    T: AsRef<JsValue>,
+{
+    type Output = Result<EventStream, Error>;
+    type IntoFuture = SubscribeFuture;
+
+    #[inline]
+    fn into_future(self) -> Self::IntoFuture {
+        let queue = Rc::new(RefCell::new(Queue {
+            bound: self.bound,
+            ..Queue::default()
+        }));
+
+        let listen = Listen {
+            event: self.event,
+            handler: {
+                let queue = queue.clone();
+                move |ev| queue.borrow_mut().push(ev)
+            },
+            target: self.target,
+        };
+
+        SubscribeFuture {
+            queue: Some(queue),
+            listen: listen.into_future(),
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+#[derive(Default)]
+struct Queue {
+    events: VecDeque<Event>,
+    waker: Option<Waker>,
+    bound: Option<Bound>,
+    dropped: u64,
+}
+
+#[cfg(feature = "stream")]
+impl Queue {
+    fn push(&mut self, ev: Event) {
+        if let Some(bound) = self.bound {
+            if self.events.len() >= bound.capacity {
+                self.dropped = self.dropped.saturating_add(1);
+                match bound.overflow {
+                    Overflow::DropOldest => _ = self.events.pop_front(),
+                    Overflow::DropNewest => return,
+                }
+            }
+        }
+
+        self.events.push_back(ev);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Represents the future of a [stream] operation.
+#[cfg(feature = "stream")]
+#[must_use = "this does nothing unless awaited"]
+pub struct SubscribeFuture {
+    queue: Option<Rc<RefCell<Queue>>>,
+    listen: ListenFuture,
+}
+
+#[cfg(feature = "stream")]
+impl Future for SubscribeFuture {
+    type Output = Result<EventStream, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.listen).poll(cx).map_ok(|unlisten| {
+            let queue = me
+                .queue
+                .take()
+                .expect("`SubscribeFuture` polled after completion");
+            EventStream { queue, unlisten }
+        })
+    }
+}
+
+/// A stream of received [events](Event).
+///
+/// When the stream is dropped, the listener is unregistered.
+#[cfg(feature = "stream")]
+pub struct EventStream {
+    queue: Rc<RefCell<Queue>>,
+    unlisten: Unlisten,
+}
+
+#[cfg(feature = "stream")]
+impl EventStream {
+    /// Returns the number of events dropped
+    /// because the [bounded stream](stream_bounded) was full.
+    ///
+    /// It's always `0` for an unbounded stream.
+    #[inline]
+    pub fn dropped(&self) -> u64 {
+        self.queue.borrow().dropped
+    }
+
+    /// Unregisters the event listener.
+    ///
+    /// Events that have already been received are discarded.
+    #[inline]
+    pub fn unlisten(self) -> UnlistenFuture {
+        self.unlisten.unlisten()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl Stream for EventStream {
+    type Item = Event;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut queue = self.queue.borrow_mut();
+        match queue.events.pop_front() {
+            Some(ev) => Poll::Ready(Some(ev)),
+            None => {
+                queue.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn invoke_listen(
+    target: Option<EventTarget<&JsValue>>,
+    event: &JsValue,
+    handler: &Handler,
+) -> Promise {
+    let (kind, label) = match target {
+        None | Some(EventTarget::Any) => (1, &JsValue::UNDEFINED),
+        Some(EventTarget::AnyLabel(s)) => (2, s),
+        Some(EventTarget::App) => (3, &JsValue::UNDEFINED),
+        Some(EventTarget::Window(s)) => (4, s),
+        Some(EventTarget::Webview(s)) => (5, s),
+        Some(EventTarget::WebviewWindow(s)) => (6, s),
+    };
+
+    let handler = match ext::try_transform_callback(handler.as_ref(), false) {
+        Ok(handler) => handler,
+        Err(e) => return Promise::reject(&e),
+    };
+
+    let cmd = LISTEN.with(|s| JsValue::from(s));
+    let args = ext::largs(event, handler, kind, label);
+    ext::invoke(&cmd, &args, None)
+}
+
+fn invoke_unlisten(event: &JsValue, id: u32) -> Promise {
+    ext::unregister_listener(event, id);
+
+    let cmd = UNLISTEN.with(|s| JsValue::from(s));
+    let args = ext::uargs(event, id);
+    ext::invoke(&cmd, &args, None)
+}
+
+/// A received [event].
+///
+/// [event]: https://v2.tauri.app/develop/calling-rust/#event-system
+///
+/// The event holds its name, the id of the listener
+/// that received it and the payload. By default, the
+/// payload is kept as a raw JS value, so it can be
+/// deserialized later with the
+#[cfg_attr(
+    feature = "serde",
+    doc = "[`deserialize`](Event::deserialize) function."
+)]
+#[cfg_attr(not(feature = "serde"), doc = "`deserialize` function.")]
+///
+/// # Empty payload
+///
+/// If the backend emits an event with the unit `()`
+/// or `None` payload, the payload is `null`.
+/// If an event is emitted from the frontend without
+/// a payload, it's `undefined`. In both cases, the payload
+/// can be deserialized into `()` or an [`Option`],
+/// but deserialization into other types fails.
+#[derive(Clone, Debug)]
+pub struct Event<P = JsValue> {
+    name: String,
+    id: u32,
+    payload: P,
+}
+
+impl Event {
+    fn from_raw(raw: &RawEvent) -> Self {
+        Self {
+            name: raw.event(),
+            id: raw.id(),
+            payload: raw.payload(),
+        }
+    }
+
+    /// Deserializes the event payload.
+    ///
+    /// Returns an [error](Error) if the payload
+    /// cannot be deserialized into `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, serde::Deserialize, tauri_wasm::event};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Progress {
+    ///     done: u32,
+    ///     total: u32,
+    /// }
+    ///
+    /// let ev = event::once("progress").await?;
+    /// let progress = ev.deserialize::<Progress>()?.into_payload();
+    /// console::log!("progress", progress.done, progress.total);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn deserialize<T>(self) -> Result<Event<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let payload = serde_wasm_bindgen::from_value(self.payload)?;
+
+        Ok(Event {
+            name: self.name,
+            id: self.id,
+            payload,
+        })
+    }
+}
+
+impl<P> Event<P> {
+    /// Returns the event name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the event listener id.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the event payload.
+    #[inline]
+    pub fn payload(&self) -> &P {
+        &self.payload
+    }
+
+    /// Returns the owned event payload.
+    #[inline]
+    pub fn into_payload(self) -> P {
+        self.payload
+    }
+}
+
+/// An argument of event target for the [`to`](Emit::to) function.
+///
+/// The type is also re-exported from the crate root.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::{Emit, EventTarget};
+///
+/// let emit: Emit<_, _> = tauri_wasm::emit_str("file-selected", "/path/to/file")
+///     .to(EventTarget::from("editor"));
+///
+/// emit.await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Labels
+///
+/// Tauri targets windows and webviews by their labels only,
+/// there is no way to target them by a numeric id. A string
+/// converted into the target with [`From`] becomes the
+/// [`AnyLabel`](EventTarget::AnyLabel) target, the same way the
+/// JS `emitTo` function treats a string. Use the explicit
+/// constructors like [`window`](EventTarget::window) to target
+/// only a specific kind of window or webview.
+///
+/// # Comparison
+///
+/// Targets can be compared, which is useful to check
+/// the routing in tests.
+///
+/// ```
+/// use tauri_wasm::EventTarget;
+///
+/// assert_eq!(EventTarget::window("app"), EventTarget::Window("app"));
+/// assert_ne!(EventTarget::window("app"), EventTarget::webview("app"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventTarget<S> {
+    /// Any target.
+    Any,
+
+    /// Any window, webview or webview window with the label.
+    AnyLabel(S),
+
+    /// The app.
+    App,
+
+    /// A window with the label.
+    Window(S),
+
+    /// A webview with the label.
+    Webview(S),
+
+    /// A webview window with the label.
+    WebviewWindow(S),
+}
+
+impl<S> EventTarget<S> {
+    /// Creates a target of anything with the given label.
+    ///
+    /// This is the same as [`any_label`](EventTarget::any_label).
+    #[inline]
+    pub fn from_string(s: S) -> Self {
+        Self::AnyLabel(s)
+    }
+
+    /// Creates a target of anything with the given label.
+    ///
+    /// The event is delivered to windows, webviews and
+    /// webview windows, whichever has the label.
+    #[inline]
+    pub fn any_label(label: S) -> Self {
+        Self::AnyLabel(label)
+    }
+
+    /// Creates a target of the window with the given label.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "serde", doc = "```")]
+    #[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::event::EventTarget;
+    ///
+    /// let target = EventTarget::window("editor");
+    /// tauri_wasm::emit("file-selected", "/path/to/file")?.to(target).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn window(label: S) -> Self {
+        Self::Window(label)
+    }
+
+    /// Creates a target of the webview with the given label.
+    #[inline]
+    pub fn webview(label: S) -> Self {
+        Self::Webview(label)
+    }
+
+    /// Creates a target of the webview window with the given label.
+    #[inline]
+    pub fn webview_window(label: S) -> Self {
+        Self::WebviewWindow(label)
+    }
+
+    #[inline]
+    pub fn as_ref(&self) -> EventTarget<&S> {
+        match self {
+            Self::Any => EventTarget::Any,
+            Self::AnyLabel(s) => EventTarget::AnyLabel(s),
+            Self::App => EventTarget::App,
             Self::Window(s) => EventTarget::Window(s),
             Self::Webview(s) => EventTarget::Webview(s),
             Self::WebviewWindow(s) => EventTarget::WebviewWindow(s),
@@ -259,3 +2048,10 @@ impl From<&str> for EventTarget<JsString> {
         Self::from_string(JsString::from(s))
     }
 }
+
+impl From<String> for EventTarget<JsString> {
+    #[inline]
+    fn from(s: String) -> Self {
+        Self::from(s.as_str())
+    }
+}
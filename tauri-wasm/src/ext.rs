@@ -1,4 +1,8 @@
-use {crate::invoke::Options, js_sys::Promise, wasm_bindgen::prelude::*};
+use {
+    crate::{error::Error, invoke::Options, string::ToStringValue},
+    js_sys::{Function, Object, Promise},
+    wasm_bindgen::prelude::*,
+};
 
 #[wasm_bindgen(module = "/core.js")]
 extern "C" {
@@ -19,14 +23,252 @@ extern "C" {
     /// ```
     pub fn is_tauri() -> bool;
 
+    pub(crate) fn invoke(cmd: &JsValue, args: &JsValue, opts: Option<Options>) -> Promise;
+
+    fn set_invoke(f: Option<&Function>);
+
+    fn has_invoke() -> bool;
+
+    #[wasm_bindgen(js_name = convert_file_src)]
+    fn convert_file_src_js(path: &JsValue, protocol: &str) -> String;
+
+    #[wasm_bindgen(js_name = transform_callback)]
+    fn transform_callback_js(f: &JsValue, once: bool) -> u32;
+
+    #[wasm_bindgen(catch, js_name = transform_callback)]
+    pub(crate) fn try_transform_callback(f: &JsValue, once: bool) -> Result<u32, JsValue>;
+
     pub(crate) fn eargs(event: &JsValue, payload: &JsValue, k: u32, l: &JsValue) -> JsValue;
+
+    pub(crate) fn hget(h: &JsValue, k: &str) -> JsValue;
+
+    pub(crate) fn hlen(h: &JsValue) -> u32;
+
+    #[cfg(feature = "headers")]
+    pub(crate) fn record(h: &JsValue) -> JsValue;
+
+    pub(crate) type Ordered;
+
+    #[wasm_bindgen(method, getter)]
+    pub(crate) fn id(this: &Ordered) -> u32;
+
+    #[wasm_bindgen(catch)]
+    pub(crate) fn ordered(f: &JsValue) -> Result<Ordered, JsValue>;
+
+    pub(crate) fn close(cb: &JsValue);
+
+    pub(crate) fn timer(ms: f64) -> Promise;
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn now() -> f64;
+
+    pub(crate) fn current_label() -> Option<String>;
+
+    pub(crate) fn wargs(label: &JsValue, value: &JsValue) -> JsValue;
+
+    #[cfg(feature = "clipboard")]
+    pub(crate) fn cargs(text: &str) -> JsValue;
+
+    #[cfg(feature = "http")]
+    pub(crate) fn fargs(method: &str, url: &str, headers: &JsValue, data: &JsValue) -> JsValue;
+
+    #[cfg(feature = "http")]
+    pub(crate) fn rargs(rid: u32) -> JsValue;
+
+    #[cfg(feature = "process")]
+    pub(crate) fn pargs(code: i32) -> JsValue;
+
+    pub(crate) fn unpack(headers: &str) -> Object;
+
+    pub(crate) fn unregister_listener(event: &JsValue, id: u32);
+
+    pub(crate) fn largs(event: &JsValue, handler: u32, k: u32, l: &JsValue) -> JsValue;
+
+    pub(crate) fn uargs(event: &JsValue, id: u32) -> JsValue;
+
+    #[cfg(feature = "abort")]
+    pub(crate) fn aborted(signal: &web_sys::AbortSignal) -> Promise;
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock(f: &JsValue);
+}
+
+/// Checks whether tauri environment is detected
+/// and caches the result.
+///
+/// Unlike [`is_tauri`], this function performs the check
+/// only once and then returns the memoized value,
+/// so it's cheap to call repeatedly. Use [`is_tauri`]
+/// if the environment might change at runtime.
+///
+/// The result is cached in a thread-local value.
+/// Since wasm32 is single-threaded by default, the check
+/// is performed once per program. If the program uses
+/// multiple threads, the check is performed once per thread.
+///
+/// # Example
+///
+/// ```
+/// # fn e() {
+/// use gloo::console;
+///
+/// if tauri_wasm::is_tauri_cached() {
+///     console::log!("tauri was detected!");
+/// }
+/// # }
+/// ```
+#[inline]
+pub fn is_tauri_cached() -> bool {
+    thread_local! {
+        static IS_TAURI: bool = is_tauri();
+    }
+
+    IS_TAURI.with(|&b| b)
+}
+
+/// Overrides the function used to invoke commands.
+///
+/// By default, commands are invoked through the tauri global API.
+/// When the function is set, all invoke and emit operations call it
+/// instead with the command name, its arguments and the options object.
+/// The value the function returns or a promise resolves with becomes the
+/// result of the operation, and an exception or a rejection becomes an error.
+///
+/// Since the function replaces the tauri host, operations don't check
+/// whether [tauri environment is detected](is_tauri) while it's set.
+/// This is useful for unusual host integrations and for testing.
+/// Call [`reset_invoke_fn`] to restore the default behavior.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {js_sys::Function, wasm_bindgen::JsValue};
+///
+/// let echo = Function::new_with_args("cmd, args", "return args");
+/// tauri_wasm::set_invoke_fn(echo);
+///
+/// let message = tauri_wasm::invoke("hello").with_args(JsValue::from("hi")).await?;
+/// assert_eq!(message, "hi");
+///
+/// tauri_wasm::reset_invoke_fn();
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn set_invoke_fn(f: Function) {
+    set_invoke(Some(&f));
+}
+
+/// Restores the default function used to invoke commands.
+///
+/// See [`set_invoke_fn`] for details.
+#[inline]
+pub fn reset_invoke_fn() {
+    set_invoke(None);
+}
+
+pub(crate) fn ensure_tauri() -> Result<(), Error> {
+    if is_tauri() || has_invoke() {
+        Ok(())
+    } else {
+        Err(Error::not_tauri())
+    }
+}
+
+/// Converts a file path to an URL that can be loaded by the webview.
+///
+/// The `protocol` is the name of the custom protocol
+/// to use, by default it's `"asset"`.
+///
+/// Note that the URL depends on the platform. On Windows and
+/// Android, custom protocols are served with the `https` scheme,
+/// so the URL looks like `https://asset.localhost/<path>`.
+/// On other platforms the URL is `asset://localhost/<path>`.
+/// The path is URL-encoded in both cases.
+///
+/// # Example
+///
+/// ```
+/// # fn e() {
+/// use gloo::console;
+///
+/// let url = tauri_wasm::convert_file_src("/path/to/image.png", None);
+/// console::log!("image url", url);
+/// # }
+/// ```
+///
+/// # Configuration
+///
+/// To load files with the asset protocol, the `protocol-asset`
+/// feature of the `tauri` crate should be enabled and the
+/// protocol should be configured in your `Tauri.toml`, like:
+///
+/// ```toml
+/// [app.security.asset-protocol]
+/// enable = true
+/// scope = ["$APPDATA/**"]
+/// ```
+#[inline]
+pub fn convert_file_src<P>(path: P, protocol: Option<&str>) -> String
+where
+    P: ToStringValue,
+{
+    let path = path.to_string_value();
+    convert_file_src_js(path.as_ref(), protocol.unwrap_or("asset"))
+}
+
+/// Registers a callback and returns its id.
+///
+/// This is a low-level primitive behind tauri channels
+/// and event listeners. Some plugins expect a callback id
+/// in the invoke arguments, so you can register a callback
+/// with this function and pass the returned id yourself.
+///
+/// # Example
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, std::collections::HashMap};
+///
+/// let id = tauri_wasm::transform_callback(
+///     |value| console::log!("received from plugin", value),
+///     true,
+/// );
+///
+/// let args = tauri_wasm::args(&HashMap::from([("callback", id)]))?;
+/// tauri_wasm::invoke("plugin:custom|subscribe").with_args(args).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Lifetime
+///
+/// The callback is stored in the tauri JS runtime. If `once`
+/// is `true`, the callback is removed after the first call,
+/// so it's released once invoked. Otherwise, it stays registered
+/// and is never released by the frontend, so calling this
+/// function in a loop leaks memory. To avoid leaks, register
+/// long-living callbacks only once, or prefer the higher level
+/// API like [`listen`](crate::event::listen) which manages
+/// the callback lifetime itself.
+#[inline]
+pub fn transform_callback<F>(callback: F, once: bool) -> u32
+where
+    F: FnMut(JsValue) + 'static,
+{
+    let f = if once {
+        Closure::once_into_js(callback)
+    } else {
+        Closure::<dyn FnMut(JsValue)>::new(callback).into_js_value()
+    };
+
+    transform_callback_js(&f, once)
 }
 
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
-    pub(crate) fn invoke(cmd: &JsValue, args: &JsValue, opts: Options) -> Promise;
-
     #[wasm_bindgen(js_name = String)]
     pub(crate) fn to_string(value: &JsValue) -> String;
 }
@@ -1,5 +1,5 @@
 use {
-    crate::{Error, invoke::IntoHeaders},
+    crate::{Error, ext, invoke::IntoHeaders},
     wasm_bindgen::JsValue,
     web_sys::Headers,
 };
@@ -7,6 +7,6 @@ use {
 impl IntoHeaders for Headers {
     #[inline]
     fn into_headers(self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self))
+        Ok(ext::record(&self))
     }
 }
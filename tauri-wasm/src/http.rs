@@ -0,0 +1,282 @@
+//! Bindings of the [HTTP plugin].
+//!
+//! [HTTP plugin]: https://v2.tauri.app/plugin/http-client/
+//!
+//! The plugin performs HTTP requests on the backend,
+//! so they aren't restricted by the webview's CORS policy.
+//!
+//! # Capabilities
+//!
+//! The plugin should be installed on the backend and
+//! the requested URLs should be allowed in the `http:default`
+//! permission scope. For example, your `Tauri.toml`
+//! config should include something like:
+//!
+//! ```toml
+//! [[app.security.capabilities]]
+//! identifier = "default"
+//! windows = ["app"]
+//! permissions = [
+//!     { identifier = "http:default", allow = [{ url = "https://*.tauri.app" }] },
+//! ]
+//! ```
+
+use {
+    crate::{
+        error::Error,
+        ext,
+        invoke::{Options, api::invoke},
+    },
+    js_sys::{Array, TypeError, Uint8Array},
+    wasm_bindgen::prelude::*,
+};
+
+#[wasm_bindgen]
+extern "C" {
+    type RawResponse;
+
+    #[wasm_bindgen(method, getter)]
+    fn status(this: &RawResponse) -> u16;
+
+    #[wasm_bindgen(method, getter = statusText)]
+    fn status_text(this: &RawResponse) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn url(this: &RawResponse) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn headers(this: &RawResponse) -> Array;
+
+    #[wasm_bindgen(method, getter)]
+    fn rid(this: &RawResponse) -> JsValue;
+}
+
+/// An HTTP request.
+///
+/// The request is sent with the [`fetch`] function.
+pub struct Request {
+    method: String,
+    url: String,
+    headers: JsValue,
+    body: Option<Vec<u8>>,
+}
+
+impl Request {
+    /// Creates a `GET` request to the URL.
+    #[inline]
+    pub fn new<U>(url: U) -> Self
+    where
+        U: Into<String>,
+    {
+        Self {
+            method: "GET".to_owned(),
+            url: url.into(),
+            headers: JsValue::UNDEFINED,
+            body: None,
+        }
+    }
+
+    /// Sets the request method.
+    #[inline]
+    pub fn with_method(self, method: &str) -> Self {
+        let method = method.to_owned();
+        Self { method, ..self }
+    }
+
+    /// Sets the request headers.
+    ///
+    /// Headers are taken from the [options](Options),
+    /// so they can be built the same way as invoke headers.
+    #[inline]
+    pub fn with_headers(self, opts: Options) -> Self {
+        let headers = opts.headers;
+        Self { headers, ..self }
+    }
+
+    /// Sets the request body.
+    #[inline]
+    pub fn with_body<B>(self, body: B) -> Self
+    where
+        B: Into<Vec<u8>>,
+    {
+        let body = Some(body.into());
+        Self { body, ..self }
+    }
+}
+
+/// An HTTP response.
+pub struct Response {
+    status: u16,
+    status_text: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// Returns the status code.
+    #[inline]
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the status message.
+    #[inline]
+    pub fn status_text(&self) -> &str {
+        &self.status_text
+    }
+
+    /// Checks whether the status code is successful,
+    /// i.e. in the range `200..300`.
+    #[inline]
+    pub fn ok(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the final URL of the response.
+    #[inline]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the response headers.
+    #[inline]
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the value of the first header with the given name.
+    ///
+    /// The name is compared case-insensitively.
+    #[inline]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, val)| val.as_str())
+    }
+
+    /// Returns the response body.
+    #[inline]
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns the owned response body.
+    #[inline]
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+
+    /// Returns the response body as a text.
+    ///
+    /// Returns an [error](Error) if the body isn't valid UTF-8.
+    #[inline]
+    pub fn text(self) -> Result<String, Error> {
+        String::from_utf8(self.body).map_err(|_| {
+            let e = TypeError::new("the response body is not valid utf-8");
+            Error::js(JsValue::from(e))
+        })
+    }
+}
+
+/// Sends an HTTP request through the backend.
+///
+/// The future resolves when the whole body is received.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {
+///     gloo::console,
+///     tauri_wasm::http::{self, Request},
+/// };
+///
+/// let res = http::fetch(Request::new("https://tauri.app")).await?;
+/// console::log!("status", res.status());
+/// console::log!("body", res.text()?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Sending a JSON body
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::{
+///     http::{self, Request},
+///     invoke::Options,
+/// };
+///
+/// let opts = Options::from_record([("content-type", "application/json")])?;
+/// let req = Request::new("https://example.com/api/users")
+///     .with_method("POST")
+///     .with_headers(opts)
+///     .with_body(r#"{"name":"anon"}"#);
+///
+/// let res = http::fetch(req).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn fetch(request: Request) -> Result<Response, Error> {
+    let body = match &request.body {
+        Some(body) => JsValue::from(Uint8Array::from(&body[..])),
+        None => JsValue::UNDEFINED,
+    };
+
+    let args = ext::fargs(&request.method, &request.url, &request.headers, &body);
+    let rid = invoke("plugin:http|fetch").with_args(args).await?;
+    let rid = to_rid(&rid)?;
+
+    let raw = invoke("plugin:http|fetch_send")
+        .with_args(ext::rargs(rid))
+        .await?;
+
+    if !raw.is_object() {
+        let e = TypeError::new("the http response is not an object");
+        return Err(Error::js(JsValue::from(e)));
+    }
+
+    let raw: RawResponse = raw.unchecked_into();
+
+    let headers = raw
+        .headers()
+        .iter()
+        .map(|pair| {
+            let pair: Array = pair.unchecked_into();
+            let key = pair.get(0).as_string().unwrap_or_default();
+            let val = pair.get(1).as_string().unwrap_or_default();
+            (key, val)
+        })
+        .collect();
+
+    let body = invoke("plugin:http|fetch_read_body")
+        .with_args(ext::rargs(to_rid(&raw.rid())?))
+        .bytes()
+        .await?;
+
+    Ok(Response {
+        status: raw.status(),
+        status_text: raw.status_text(),
+        url: raw.url(),
+        headers,
+        body,
+    })
+}
+
+/// Converts a resource id of the plugin.
+///
+/// A malformed id isn't replaced with a default one,
+/// so the request never reads another resource.
+fn to_rid(value: &JsValue) -> Result<u32, Error> {
+    match value.as_f64() {
+        Some(rid) if rid >= 0. && rid <= f64::from(u32::MAX) && rid.fract() == 0. => Ok(rid as u32),
+        _ => {
+            let e = TypeError::new("the http resource id is not a valid number");
+            Err(Error::js(JsValue::from(e)))
+        }
+    }
+}
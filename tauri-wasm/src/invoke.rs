@@ -3,16 +3,41 @@
 //! [commands]: https://v2.tauri.app/develop/calling-rust/#commands
 
 use {
-    crate::{error::Error, ext, string::ToStringValue},
-    js_sys::{ArrayBuffer, Uint8Array},
+    crate::{error::Error, ext, response, string::ToStringValue},
+    js_sys::{Array, ArrayBuffer, JsString, Object, Promise, Reflect, TypeError, Uint8Array},
     std::{
+        any::Any,
+        fmt,
+        marker::PhantomData,
         pin::Pin,
         task::{Context, Poll},
+        time::Duration,
     },
     wasm_bindgen::prelude::*,
     wasm_bindgen_futures::JsFuture,
 };
 
+#[cfg(feature = "abort")]
+use web_sys::{AbortController, AbortSignal};
+
+#[cfg(feature = "tracing")]
+use crate::trace::Trace;
+
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
+pub use crate::{
+    channel::Channel,
+    response::JsResponse,
+    retry::{Retry, retry},
+};
+
+#[cfg(feature = "blob")]
+pub use crate::blob::read_blob;
+
+#[cfg(feature = "serde")]
+pub use crate::serde::Json;
+
 pub(crate) mod api {
     use super::*;
 
@@ -25,6 +50,11 @@ pub(crate) mod api {
     /// See [`with_args`](Invoke::with_args) and
     /// [`with_options`](Invoke::with_options) for details.
     ///
+    /// A call without arguments and options is already
+    /// the cheapest path. The `undefined` arguments are passed
+    /// as a constant and no options object is created,
+    /// so there is no need for a separate fast path function.
+    ///
     /// # Example
     ///
     /// ```
@@ -42,20 +72,256 @@ pub(crate) mod api {
         C: ToStringValue,
     {
         let cmd = cmd.to_string_value();
-        let args = JsValue::UNDEFINED;
+        let args = Ok(JsValue::UNDEFINED);
         let opts = Options::empty();
-        Invoke { cmd, args, opts }
+        Invoke {
+            cmd,
+            args,
+            opts,
+            progress: None,
+            timeout: None,
+            #[cfg(feature = "abort")]
+            signal: None,
+            response: PhantomData,
+        }
+    }
+}
+
+/// Runs an [invoke](api::invoke) operation in the background.
+///
+/// The operation is started immediately with [`spawn_local`],
+/// so it can be used outside of async code, for example, in
+/// a click handler. Any configuration of the [`Invoke`]
+/// builder still applies.
+///
+/// [`spawn_local`]: wasm_bindgen_futures::spawn_local
+///
+/// The result of the operation is ignored, including errors.
+/// To report an error, attach a handler with
+/// [`inspect_err`](Invoke::inspect_err) before spawning.
+///
+/// # Example
+///
+/// ```
+/// # fn e() {
+/// use {gloo::console, tauri_wasm::invoke};
+///
+/// invoke::spawn(
+///     tauri_wasm::invoke("save")
+///         .inspect_err(|e| console::error!("failed to save", e.to_string())),
+/// );
+/// # }
+/// ```
+#[inline]
+pub fn spawn<I, T>(invoke: I)
+where
+    I: IntoFuture<Output = Result<T, Error>, IntoFuture: 'static>,
+{
+    let fut = invoke.into_future();
+    wasm_bindgen_futures::spawn_local(async {
+        _ = fut.await;
+    });
+}
+
+/// Invokes a [command] on the backend without any conversions.
+///
+/// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+///
+/// This is the low-level call the [invoke](api::invoke) builder
+/// is built on. The values are passed to the tauri IPC as is,
+/// so it's useful to build custom abstractions over commands.
+/// The returned future resolves with a raw result and can be
+/// extended the same way as the one of the [`Invoke`] builder,
+/// for example, with the [`bytes`](InvokeFuture::bytes) function.
+///
+/// The values must follow the same invariants the builder
+/// upholds, otherwise the call is rejected by the IPC:
+///
+/// - The `cmd` is a JS string with the command name.
+/// - The `args` is `undefined` for no arguments, an object
+///   whose fields are command parameters, or an [`ArrayBuffer`],
+///   a typed array or an array passed as a raw request body.
+///
+/// A timeout, an abort signal or a progress channel can't be
+/// set for the call. To pass a [channel](Channel), set it as an
+/// argument field like the [`with_progress`](Invoke::with_progress)
+/// function does.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {
+///     js_sys::Uint8Array,
+///     tauri_wasm::invoke::{self, Options},
+///     wasm_bindgen::JsValue,
+/// };
+///
+/// let cmd = JsValue::from_str("upload");
+/// let args = Uint8Array::from(&[1, 2, 3][..]);
+/// invoke::raw(&cmd, &args, Options::empty()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn raw(cmd: &JsValue, args: &JsValue, opts: Options) -> InvokeFuture {
+    #[cfg(feature = "tracing")]
+    let trace = Trace::invoke(cmd);
+
+    InvokeFuture {
+        state: State::start(|| ext::invoke(cmd, args, opts.non_empty())),
+        _progress: None,
+        timeout: None,
+        #[cfg(feature = "abort")]
+        abort: None,
+        #[cfg(feature = "tracing")]
+        trace,
     }
 }
 
 /// A type used to configure an [invoke](api::invoke) operation.
-pub struct Invoke<C, A = JsValue> {
+///
+/// # Response type
+///
+/// The `R` parameter is the [kind](ResponseKind) of the result
+/// the invoke operation resolves with when awaited. By default,
+/// it's a raw [`JsValue`]. Use the [`returning`](Invoke::returning)
+#[cfg_attr(
+    feature = "serde",
+    doc = "function to change it, or the [`typed`](Invoke::typed) function"
+)]
+#[cfg_attr(
+    not(feature = "serde"),
+    doc = "function to change it, or the `typed` function"
+)]
+/// to deserialize the result. The response type is kept when the
+/// builder is configured further, so it can be set up front.
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let user: User = tauri_wasm::invoke("get_user")
+///     .typed()
+///     .with_args(tauri_wasm::named_args("id", &37)?)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+#[cfg_attr(
+    feature = "serde",
+    doc = "Deserializable types are wrapped into the [`Typed`] marker"
+)]
+#[cfg_attr(
+    not(feature = "serde"),
+    doc = "Deserializable types are wrapped into the `Typed` marker"
+)]
+/// instead of being used as the response type directly. A blanket
+/// implementation for all `DeserializeOwned` types would overlap
+/// with the [`JsValue`] implementation, so the marker keeps the
+/// raw and the typed results apart.
+///
+/// # Builder order
+///
+/// The builder functions can be called in any order. Each one
+/// sets its own part of the operation and keeps the others,
+/// so these invoke calls are the same:
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {std::time::Duration, tauri_wasm::invoke::Options};
+///
+/// let opts = Options::from_record([("token", "7")])?;
+/// let a = tauri_wasm::invoke("send")
+///     .with_args(&[1, 2, 3])
+///     .with_options(opts)
+///     .with_timeout(Duration::from_secs(1));
+///
+/// let opts = Options::from_record([("token", "7")])?;
+/// let b = tauri_wasm::invoke("send")
+///     .with_timeout(Duration::from_secs(1))
+///     .with_options(opts)
+///     .with_args(&[1, 2, 3]);
+///
+/// assert_eq!(a.await?, b.await?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Calling the same function again replaces the previous
+/// value instead of merging them. For example, only the
+/// last arguments passed to [`with_args`](Invoke::with_args)
+/// are sent to the command. To merge arguments, use the
+/// [`with_args_merged`](Invoke::with_args_merged) function.
+#[must_use = "this does nothing unless awaited"]
+pub struct Invoke<C, A = JsValue, R = JsValue> {
     cmd: C,
-    args: A,
+    args: Result<A, Error>,
     opts: Options,
+    progress: Option<Progress>,
+    timeout: Option<Duration>,
+    #[cfg(feature = "abort")]
+    signal: Option<AbortSignal>,
+    response: PhantomData<fn() -> R>,
+}
+
+impl<C, A, R> fmt::Debug for Invoke<C, A, R>
+where
+    C: fmt::Debug,
+    A: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("Invoke");
+        s.field("cmd", &self.cmd)
+            .field("args", &self.args)
+            .field("opts", &self.opts)
+            .field("progress", &self.progress.is_some())
+            .field("timeout", &self.timeout);
+
+        #[cfg(feature = "abort")]
+        s.field("signal", &self.signal);
+
+        s.finish()
+    }
+}
+
+impl<C, A, R> Invoke<C, A, R>
+where
+    C: AsRef<JsValue>,
+{
+    /// Returns the [command] name.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is useful to log the command before it's invoked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// let invoke = tauri_wasm::invoke("connect");
+    /// console::log!("invoking", invoke.command());
+    /// let message = invoke.await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn command(&self) -> &JsValue {
+        self.cmd.as_ref()
+    }
 }
 
-impl<C, A> Invoke<C, A> {
+impl<C, A, R> Invoke<C, A, R> {
     /// Invokes a [command] with arguments on the backend.
     ///
     /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
@@ -63,7 +329,11 @@ impl<C, A> Invoke<C, A> {
     /// # Passing a serializable type
     ///
     /// To send a custom serializable type as arguments,
-    /// use the helper [`args`](crate::args) function.
+    #[cfg_attr(
+        feature = "serde",
+        doc = "use the helper [`args`](crate::args) function."
+    )]
+    #[cfg_attr(not(feature = "serde"), doc = "use the helper `args` function.")]
     ///
     #[cfg_attr(feature = "serde", doc = "```")]
     #[cfg_attr(not(feature = "serde"), doc = "```ignore")]
@@ -98,7 +368,7 @@ impl<C, A> Invoke<C, A> {
     /// # async fn e() -> Result<(), tauri_wasm::Error> {
     /// use {
     ///     gloo::console,
-    ///     tauri_wasm::invoke::ToArgs,
+    ///     tauri_wasm::{Error, invoke::ToArgs},
     ///     wasm_bindgen::prelude::*,
     /// };
     ///
@@ -111,10 +381,10 @@ impl<C, A> Invoke<C, A> {
     /// impl ToArgs for User {
     ///     type Js = JsValue;
     ///
-    ///     fn to_args(self) -> Self::Js {
+    ///     fn to_args(self) -> Result<Self::Js, Error> {
     ///         // wasm_bindgen attribute implements
     ///         // convertion into JS value
-    ///         JsValue::from(self)
+    ///         Ok(JsValue::from(self))
     ///     }
     /// }
     ///
@@ -128,26 +398,149 @@ impl<C, A> Invoke<C, A> {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// If the conversion into arguments fails,
+    /// the invoke future resolves with the error.
+    ///
+    /// # Replacing arguments
+    ///
+    /// A later call replaces the arguments set before, even if
+    /// they have different types. The arguments aren't merged:
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// // only the `[4, 5]` bytes are sent
+    /// tauri_wasm::invoke("upload")
+    ///     .with_args(&[1, 2, 3])
+    ///     .with_args(vec![4, 5])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    pub fn with_args<T>(self, args: T) -> Invoke<C, T::Js>
+    pub fn with_args<T>(self, args: T) -> Invoke<C, T::Js, R>
     where
         T: ToArgs,
     {
         let cmd = self.cmd;
         let args = args.to_args();
         let opts = self.opts;
-        Invoke { cmd, args, opts }
+        Invoke {
+            cmd,
+            args,
+            opts,
+            progress: self.progress,
+            timeout: self.timeout,
+            #[cfg(feature = "abort")]
+            signal: self.signal,
+            response: PhantomData,
+        }
     }
 
-    /// Invokes a [command] with options on the backend.
+    /// Invokes a [command] with arguments merged
+    /// into the previous ones on the backend.
     ///
     /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
     ///
+    /// Unlike [`with_args`](Invoke::with_args), the arguments are
+    /// added to the ones set before, like with `Object.assign`.
+    /// So different parts of code can contribute their own
+    /// parameters to the same invoke call. If both arguments
+    /// have the same field, the new value wins. The previous
+    /// arguments aren't modified, the merged ones are a new object.
+    ///
+    /// If there are no previous arguments, the new ones are used
+    /// as is. Otherwise, both must be objects of named parameters.
+    /// Raw request bodies like bytes and arrays can't be merged,
+    /// so the invoke future resolves with an [error](Error) then.
+    /// A conversion error of either arguments is kept the same way.
+    ///
     /// # Example
     ///
     #[cfg_attr(feature = "serde", doc = "```")]
     #[cfg_attr(not(feature = "serde"), doc = "```ignore")]
     /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// // sends `{ id: 37, force: true }`
+    /// tauri_wasm::invoke("remove")
+    ///     .with_args(tauri_wasm::named_args("id", &37)?)
+    ///     .with_args_merged(tauri_wasm::named_args("force", &true)?)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_args_merged<T>(self, args: T) -> Invoke<C, JsValue, R>
+    where
+        A: AsRef<JsValue>,
+        T: ToArgs,
+    {
+        let args = self.args.and_then(|prev| {
+            let next = args.to_args()?;
+            merge_args(prev.as_ref(), next.as_ref())
+        });
+
+        Invoke {
+            cmd: self.cmd,
+            args,
+            opts: self.opts,
+            progress: self.progress,
+            timeout: self.timeout,
+            #[cfg(feature = "abort")]
+            signal: self.signal,
+            response: PhantomData,
+        }
+    }
+
+    /// Sets the [response type](Invoke#response-type)
+    /// the invoke operation resolves with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// let message: String = tauri_wasm::invoke("hello").returning::<String>().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn returning<S>(self) -> Invoke<C, A, S>
+    where
+        S: ResponseKind,
+    {
+        Invoke {
+            cmd: self.cmd,
+            args: self.args,
+            opts: self.opts,
+            progress: self.progress,
+            timeout: self.timeout,
+            #[cfg(feature = "abort")]
+            signal: self.signal,
+            response: PhantomData,
+        }
+    }
+
+    /// Sets the [response type](Invoke#response-type)
+    /// to the deserialized `T` value.
+    ///
+    /// This is a shortcut for the [`returning`](Invoke::returning)
+    /// function with the [`Typed`] response type.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn typed<T>(self) -> Invoke<C, A, Typed<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.returning()
+    }
+
+    /// Invokes a [command] with options on the backend.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
     /// use {gloo::console, tauri_wasm::invoke::Options};
     ///
     /// let opts = Options::from_record([
@@ -160,78 +553,1212 @@ impl<C, A> Invoke<C, A> {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// A later call replaces the options set before,
+    /// the headers of both options aren't merged.
     #[inline]
     pub fn with_options(self, opts: Options) -> Self {
         Self { opts, ..self }
     }
-}
 
-/// Represents the future of an [invoke](api::invoke) operation.
-pub struct InvokeFuture(JsFuture);
+    /// Invokes a [command] on the backend and
+    /// reports its progress to the handler.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// A new [`Channel`] is passed to the command as the
+    /// `onProgress` argument, so the backend command takes
+    /// the `on_progress: Channel<_>` parameter. Every message
+    /// sent through the channel is passed to the handler,
+    /// and the command result resolves the invoke future.
+    ///
+    /// The channel is added to the rest of [arguments] in
+    /// any call order. The arguments must be an object or
+    /// absent, otherwise the invoke future resolves with
+    /// an error. The passed object itself is not modified.
+    ///
+    /// [arguments]: Invoke::with_args
+    ///
+    /// # Example
+    ///
+    /// Assume the backend command reports a progress:
+    ///
+    /// ```ignore
+    /// #[tauri::command]
+    /// fn upload(path: String, on_progress: tauri::ipc::Channel<u32>) {
+    ///     for percent in 0..=100 {
+    ///         _ = on_progress.send(percent);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Then you can handle the progress on the frontend:
+    ///
+    #[cfg_attr(feature = "serde", doc = "```")]
+    #[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, serde::Serialize};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Args<'str> {
+    ///     path: &'str str,
+    /// }
+    ///
+    /// let args = tauri_wasm::args(&Args { path: "data.bin" })?;
+    /// let message = tauri_wasm::invoke("upload")
+    ///     .with_args(args)
+    ///     .with_progress(|percent| console::log!("uploaded", percent))
+    ///     .await?;
+    ///
+    /// console::log!("upload is done", message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_progress<F>(self, f: F) -> Self
+    where
+        F: FnMut(JsValue) + 'static,
+    {
+        let progress: Progress = Box::new(|| {
+            let channel = Channel::with_decode(Ok)?;
+            channel.on_message(f);
+            Ok(ProgressChannel::new(channel))
+        });
 
-impl InvokeFuture {
-    /// Returns the inner future.
+        Self {
+            progress: Some(progress),
+            ..self
+        }
+    }
+
+    /// Invokes a [command] on the backend and reports
+    /// its deserialized progress to the handler.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is like the [`with_progress`](Invoke::with_progress)
+    /// function, but each message is deserialized into `T` the
+    /// same way as a [typed](Invoke::typed) result. Messages that
+    /// cannot be deserialized are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// let args = tauri_wasm::named_args("path", "/path/to/file")?;
+    /// tauri_wasm::invoke("upload")
+    ///     .with_args(args)
+    ///     .with_typed_progress(|percent: u32| console::log!("uploaded", percent))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
     #[inline]
-    pub fn into_future(self) -> JsFuture {
-        self.0
+    pub fn with_typed_progress<T, F>(self, f: F) -> Self
+    where
+        T: DeserializeOwned + 'static,
+        F: FnMut(T) + 'static,
+    {
+        let progress: Progress = Box::new(|| {
+            let channel = Channel::with_decode(crate::channel::decode_typed)?;
+            channel.on_message(f);
+            Ok(ProgressChannel::new(channel))
+        });
+
+        Self {
+            progress: Some(progress),
+            ..self
+        }
     }
-}
 
-impl Future for InvokeFuture {
-    type Output = Result<JsValue, Error>;
+    /// Invokes a [command] on the backend
+    /// with a time limit.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// If the command doesn't respond within the duration,
+    /// the invoke future resolves with an [error] for which
+    /// [`is_timeout`](Error::is_timeout) returns `true`.
+    /// The timer is cleared once the future completes
+    /// or is dropped.
+    ///
+    /// [error]: Error
+    ///
+    /// Note that the timeout doesn't cancel the command.
+    /// It keeps running on the backend and its result
+    /// is discarded, so only the frontend wait is limited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, std::time::Duration};
+    ///
+    /// let timeout = Duration::from_secs(5);
+    /// match tauri_wasm::invoke("connect").with_timeout(timeout).await {
+    ///     Ok(message) => console::log!("connected", message),
+    ///     Err(e) if e.is_timeout() => console::error!("the backend doesn't respond"),
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let timeout = Some(timeout);
+        Self { timeout, ..self }
+    }
 
+    /// Invokes a [command] on the backend which
+    /// can be cancelled with an abort signal.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// When the signal is aborted, the invoke future stops
+    /// waiting for the result and resolves with an [error]
+    /// for which [`is_cancelled`](Error::is_cancelled)
+    /// returns `true`. The error holds the signal's reason.
+    ///
+    /// [error]: Error
+    ///
+    /// Note that Tauri has no way to cancel a request
+    /// once it has been sent. The command keeps running
+    /// on the backend and its result is discarded,
+    /// so only the frontend wait is cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, web_sys::AbortController};
+    ///
+    /// let controller = AbortController::new().expect("create abort controller");
+    /// let signal = controller.signal();
+    ///
+    /// // abort the signal somewhere else,
+    /// // for example when a user navigates away
+    /// controller.abort();
+    ///
+    /// match tauri_wasm::invoke("download").with_signal(signal).await {
+    ///     Ok(message) => console::log!("downloaded", message),
+    ///     Err(e) if e.is_cancelled() => console::log!("download was cancelled"),
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "abort")]
     #[inline]
-    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let me = self.get_mut();
-        Pin::new(&mut me.0).poll(cx).map_err(Error)
+    pub fn with_signal(self, signal: AbortSignal) -> Self {
+        let signal = Some(signal);
+        Self { signal, ..self }
     }
 }
 
-impl<C, A> IntoFuture for Invoke<C, A>
+impl<C, A, R> Invoke<C, A, R>
 where
     C: AsRef<JsValue>,
     A: AsRef<JsValue>,
 {
-    type Output = Result<JsValue, Error>;
-    type IntoFuture = InvokeFuture;
-
+    /// Invokes a [command] on the backend and
+    /// receives the result as bytes.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is a shortcut for calling
+    /// [`bytes`](InvokeFuture::bytes)
+    /// on the invoke future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// let thumbnail = tauri_wasm::invoke("thumbnail").bytes().await?;
+    /// console::log!("thumbnail size", thumbnail.len());
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    fn into_future(self) -> Self::IntoFuture {
-        let promise = ext::invoke(self.cmd.as_ref(), self.args.as_ref(), self.opts);
-        InvokeFuture(JsFuture::from(promise))
+    pub fn bytes(self) -> BytesFuture {
+        self.start().bytes()
     }
-}
-
-/// Types that can be represented as arguments.
-pub trait ToArgs {
-    type Js: AsRef<JsValue>;
-    fn to_args(self) -> Self::Js;
-}
-
-impl ToArgs for ArrayBuffer {
-    type Js = JsValue;
 
+    /// Invokes a [command] on the backend and
+    /// receives the result as a string.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is a shortcut for calling
+    /// [`text`](InvokeFuture::text)
+    /// on the invoke future.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "serde", doc = "```")]
+    #[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// let args = tauri_wasm::named_args("message", "ping")?;
+    /// let message = tauri_wasm::invoke("hello").with_args(args).text().await?;
+    /// assert_eq!(message, "ping");
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    fn to_args(self) -> Self::Js {
-        JsValue::from(self)
+    pub fn text(self) -> TextFuture {
+        self.start().text()
     }
-}
-
-impl<'arr> ToArgs for &'arr ArrayBuffer {
-    type Js = &'arr JsValue;
 
-    #[inline]
-    fn to_args(self) -> Self::Js {
-        self
-    }
-}
+    /// Invokes a [command] on the backend and
+    /// receives the result as a [response](JsResponse).
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is a shortcut for calling
+    /// [`response`](InvokeFuture::response)
+    /// on the invoke future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// let res = tauri_wasm::invoke("status").response().await?;
+    /// match res.as_str() {
+    ///     Some(status) => console::log!("status", status),
+    ///     None => console::log!("no status"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn response(self) -> ResponseFuture {
+        self.start().response()
+    }
+
+    /// Invokes a [command] on the backend which
+    /// can be cancelled with the returned handle.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is a shortcut for creating an abort controller and
+    /// passing its signal to [`with_signal`](Invoke::with_signal).
+    /// When [`abort`](AbortHandle::abort) is called, the invoke
+    /// future resolves with the cancelled [error](Error).
+    /// Dropping the handle doesn't cancel the future.
+    ///
+    /// Note that the backend command isn't cancelled, since
+    /// tauri has no way to cancel a request once it has been
+    /// sent. Only the frontend wait is cancelled, the same
+    /// as when the invoke future is dropped.
+    ///
+    /// If the host doesn't support abort controllers,
+    /// the invoke future resolves with the [error](Error)
+    /// and the returned handle does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// let (fut, handle) = tauri_wasm::invoke("download").abortable();
+    ///
+    /// // cancel somewhere else,
+    /// // for example when a user navigates away
+    /// handle.abort();
+    ///
+    /// let Err(e) = fut.await else {
+    ///     return Ok(());
+    /// };
+    ///
+    /// assert!(e.is_cancelled(), "the invoke should be cancelled");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "abort")]
+    #[inline]
+    pub fn abortable(self) -> (InvokeFuture, AbortHandle) {
+        match AbortController::new() {
+            Ok(controller) => {
+                let fut = self.with_signal(controller.signal()).start();
+                (fut, AbortHandle(Some(controller)))
+            }
+            Err(e) => {
+                let args = Err(Error::js(e));
+                let fut = Self { args, ..self }.start();
+                (fut, AbortHandle(None))
+            }
+        }
+    }
+
+    /// Invokes a [command] on the backend and
+    /// returns the raw JS promise.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is useful to pass the promise to JS code,
+    /// for example, to return it from an exported function.
+    /// If the arguments conversion fails or tauri isn't detected,
+    /// the returned promise is rejected with the error.
+    ///
+    /// Note that the promise is the plain IPC call,
+    /// so [progress](Invoke::with_progress), timeout and
+    /// abort signal settings aren't applied to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use {js_sys::Promise, wasm_bindgen::prelude::*};
+    ///
+    /// #[wasm_bindgen]
+    /// pub fn connect() -> Promise {
+    ///     tauri_wasm::invoke("connect").into_promise()
+    /// }
+    /// ```
+    #[inline]
+    pub fn into_promise(self) -> Promise {
+        let promise = self.args.and_then(|args| {
+            ext::ensure_tauri()?;
+            let promise = ext::invoke(self.cmd.as_ref(), args.as_ref(), self.opts.non_empty());
+            Ok(promise)
+        });
+
+        promise.unwrap_or_else(|e| Promise::reject(&JsValue::from(e)))
+    }
+
+    /// Invokes a [command] on the backend and
+    /// inspects the successful result.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is a shortcut for calling
+    /// [`inspect_ok`](InvokeFuture::inspect_ok)
+    /// on the invoke future.
+    #[inline]
+    pub fn inspect_ok<F>(self, f: F) -> InspectOk<F>
+    where
+        F: FnOnce(&JsValue),
+    {
+        self.start().inspect_ok(f)
+    }
+
+    /// Invokes a [command] on the backend and
+    /// inspects the error.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is a shortcut for calling
+    /// [`inspect_err`](InvokeFuture::inspect_err)
+    /// on the invoke future.
+    #[inline]
+    pub fn inspect_err<F>(self, f: F) -> InspectErr<F>
+    where
+        F: FnOnce(&Error),
+    {
+        self.start().inspect_err(f)
+    }
+
+    /// Invokes a [command] on the backend and
+    /// deserializes the result into a typed value.
+    ///
+    /// [command]: https://v2.tauri.app/develop/calling-rust/#commands
+    ///
+    /// This is a shortcut for calling
+    /// [`deserialize`](InvokeFuture::deserialize)
+    /// on the invoke future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {serde::Deserialize, tauri_wasm::invoke};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let user: User = invoke("get_user").deserialize().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn deserialize<T>(self) -> DeserializeFuture<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.start().deserialize()
+    }
+}
+
+/// A handle to cancel an [abortable](Invoke::abortable) invoke operation.
+#[cfg(feature = "abort")]
+pub struct AbortHandle(Option<AbortController>);
+
+#[cfg(feature = "abort")]
+impl AbortHandle {
+    /// Cancels the invoke operation.
+    ///
+    /// Does nothing if the operation is already completed.
+    #[inline]
+    pub fn abort(&self) {
+        if let Some(controller) = &self.0 {
+            controller.abort();
+        }
+    }
+
+    /// Returns the abort signal.
+    ///
+    /// Returns `None` if the abort controller
+    /// couldn't be created.
+    #[inline]
+    pub fn signal(&self) -> Option<AbortSignal> {
+        self.0.as_ref().map(AbortController::signal)
+    }
+}
+
+/// Represents the future of an [invoke](api::invoke) operation.
+#[must_use = "this does nothing unless awaited"]
+pub struct InvokeFuture {
+    state: State,
+    _progress: Option<ProgressChannel>,
+    timeout: Option<Timeout>,
+    #[cfg(feature = "abort")]
+    abort: Option<Abort>,
+    #[cfg(feature = "tracing")]
+    trace: Trace,
+}
+
+pub(crate) enum State {
+    Pending(JsFuture),
+    Failed(Option<Error>),
+}
+
+impl State {
+    /// Calls the IPC function if tauri is detected.
+    pub(crate) fn start<F>(f: F) -> Self
+    where
+        F: FnOnce() -> Promise,
+    {
+        match ext::ensure_tauri() {
+            Ok(()) => Self::Pending(JsFuture::from(f())),
+            Err(e) => Self::Failed(Some(e)),
+        }
+    }
+
+    pub(crate) fn into_future(self) -> JsFuture {
+        match self {
+            Self::Pending(fut) => fut,
+            Self::Failed(e) => {
+                let value = e.map(JsValue::from).unwrap_or_default();
+                JsFuture::from(Promise::reject(&value))
+            }
+        }
+    }
+
+    pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Result<JsValue, Error>> {
+        match self {
+            Self::Pending(fut) => Pin::new(fut).poll(cx).map_err(Error::js),
+            Self::Failed(e) => {
+                let e = e.take().expect("the future polled after completion");
+                Poll::Ready(Err(e))
+            }
+        }
+    }
+}
+
+pub(crate) struct Timeout {
+    ms: f64,
+    timer: Promise,
+    fut: JsFuture,
+}
+
+impl Timeout {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        // `setTimeout` delay is limited by a 32-bit signed integer
+        let ms = timeout.as_millis().min(i32::MAX as u128) as f64;
+        let timer = ext::timer(ms);
+        let fut = JsFuture::from(timer.clone());
+        Self { ms, timer, fut }
+    }
+
+    /// Resolves with the timeout error when the timer fires.
+    pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Error> {
+        Pin::new(&mut self.fut)
+            .poll(cx)
+            .map(|_| Error::timeout(self.ms))
+    }
+}
+
+impl Drop for Timeout {
+    #[inline]
+    fn drop(&mut self) {
+        ext::close(&self.timer);
+    }
+}
+
+#[cfg(feature = "abort")]
+struct Abort {
+    signal: AbortSignal,
+    fut: JsFuture,
+}
+
+impl InvokeFuture {
+    /// Returns the inner future.
+    ///
+    /// Note that the inner future doesn't
+    #[cfg_attr(
+        feature = "abort",
+        doc = "observe the [abort signal](Invoke::with_signal)."
+    )]
+    #[cfg_attr(not(feature = "abort"), doc = "observe the abort signal.")]
+    #[inline]
+    pub fn into_future(self) -> JsFuture {
+        self.state.into_future()
+    }
+
+    /// Receives the result of the invoke operation as bytes.
+    ///
+    /// This is useful for commands that respond with raw
+    /// binary data, for example with [`tauri::ipc::Response`].
+    /// The result is expected to be an [`ArrayBuffer`]
+    /// or a [`Uint8Array`], its contents are copied into
+    /// a vector. Otherwise, an [error](Error) is returned.
+    ///
+    /// [`tauri::ipc::Response`]: https://docs.rs/tauri/latest/tauri/ipc/struct.Response.html
+    #[inline]
+    pub fn bytes(self) -> BytesFuture {
+        BytesFuture(self)
+    }
+
+    /// Receives the result of the invoke operation as a string.
+    ///
+    /// This is useful for commands that respond with a
+    /// `String` and avoids deserializing the result.
+    /// The received string can be compared with `&str`
+    /// directly, without relying on `JsValue` comparisons.
+    /// If the result is not a JS string,
+    /// an [error](Error) is returned.
+    #[inline]
+    pub fn text(self) -> TextFuture {
+        TextFuture(self)
+    }
+
+    /// Receives the result of the invoke operation
+    /// as a [response](JsResponse).
+    ///
+    /// This is useful when a command can respond with
+    /// values of different types, so the result can be
+    /// inspected with typed accessors.
+    #[inline]
+    pub fn response(self) -> ResponseFuture {
+        ResponseFuture(self)
+    }
+
+    /// Calls the function with the result of the
+    /// invoke operation if it succeeds.
+    ///
+    /// The result is passed through unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use gloo::console;
+    ///
+    /// let message = tauri_wasm::invoke("connect")
+    ///     .inspect_ok(|message| console::log!("connected", message))
+    ///     .inspect_err(|e| console::error!("failed to connect", e.to_string()))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn inspect_ok<F>(self, f: F) -> InspectOk<F>
+    where
+        F: FnOnce(&JsValue),
+    {
+        InspectOk {
+            fut: self,
+            f: Some(f),
+        }
+    }
+
+    /// Calls the function with the error of the
+    /// invoke operation if it fails.
+    ///
+    /// The result is passed through unchanged.
+    #[inline]
+    pub fn inspect_err<F>(self, f: F) -> InspectErr<F>
+    where
+        F: FnOnce(&Error),
+    {
+        InspectErr {
+            fut: self,
+            f: Some(f),
+        }
+    }
+
+    /// Deserializes the result of the invoke operation.
+    ///
+    /// Returns an [error](Error) if the operation fails
+    /// or the result cannot be deserialized into `T`.
+    ///
+    /// # Empty results
+    ///
+    /// A command that returns `()` or `None` resolves with
+    /// `null` or `undefined`. Such a result can be deserialized
+    /// into `()` or into an [`Option`] which is `None` then,
+    /// but deserialization into other types fails.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn deserialize<T>(self) -> DeserializeFuture<T>
+    where
+        T: DeserializeOwned,
+    {
+        DeserializeFuture {
+            fut: self,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl Future for InvokeFuture {
+    type Output = Result<JsValue, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+
+        #[cfg(feature = "tracing")]
+        let span = me.trace.span().clone();
+
+        #[cfg(feature = "tracing")]
+        let _enter = span.enter();
+
+        let res = me.poll_result(cx);
+
+        #[cfg(feature = "tracing")]
+        if let Poll::Ready(res) = &res {
+            me.trace.finish(res);
+        }
+
+        res
+    }
+}
+
+impl InvokeFuture {
+    fn poll_result(&mut self, cx: &mut Context<'_>) -> Poll<Result<JsValue, Error>> {
+        // the result is polled first, so a result which is
+        // ready when the timer fires or the signal is aborted wins
+        if let Poll::Ready(res) = self.state.poll(cx) {
+            // clear the timer as soon as possible
+            self.timeout = None;
+            return Poll::Ready(res);
+        }
+
+        #[cfg(feature = "abort")]
+        if let Some(abort) = &mut self.abort {
+            if abort.signal.aborted() || Pin::new(&mut abort.fut).poll(cx).is_ready() {
+                let reason = abort.signal.reason();
+                return Poll::Ready(Err(Error::cancelled(reason)));
+            }
+        }
+
+        if let Some(timeout) = &mut self.timeout {
+            if let Poll::Ready(e) = timeout.poll(cx) {
+                self.timeout = None;
+                return Poll::Ready(Err(e));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Represents the future of an [invoke](api::invoke)
+/// operation with an inspected result.
+#[must_use = "this does nothing unless awaited"]
+pub struct InspectOk<F> {
+    fut: InvokeFuture,
+    f: Option<F>,
+}
+
+// the function is never pinned
+impl<F> Unpin for InspectOk<F> {}
+
+impl<F> InspectOk<F> {
+    /// Calls the function with the error of the
+    /// invoke operation if it fails.
+    #[inline]
+    pub fn inspect_err<G>(self, f: G) -> InspectErr<G, Self>
+    where
+        G: FnOnce(&Error),
+    {
+        InspectErr {
+            fut: self,
+            f: Some(f),
+        }
+    }
+}
+
+impl<F> Future for InspectOk<F>
+where
+    F: FnOnce(&JsValue),
+{
+    type Output = Result<JsValue, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.fut).poll(cx).map(|res| {
+            if let (Ok(value), Some(f)) = (&res, me.f.take()) {
+                f(value);
+            }
+
+            res
+        })
+    }
+}
+
+/// Represents the future of an [invoke](api::invoke)
+/// operation with an inspected error.
+#[must_use = "this does nothing unless awaited"]
+pub struct InspectErr<F, I = InvokeFuture> {
+    fut: I,
+    f: Option<F>,
+}
+
+// the function is never pinned
+impl<F, I> Unpin for InspectErr<F, I> where I: Unpin {}
+
+impl<F> InspectErr<F> {
+    /// Calls the function with the result of the
+    /// invoke operation if it succeeds.
+    #[inline]
+    pub fn inspect_ok<G>(self, f: G) -> InspectErr<F, InspectOk<G>>
+    where
+        G: FnOnce(&JsValue),
+    {
+        InspectErr {
+            fut: InspectOk {
+                fut: self.fut,
+                f: Some(f),
+            },
+            f: self.f,
+        }
+    }
+}
+
+impl<F, I> Future for InspectErr<F, I>
+where
+    F: FnOnce(&Error),
+    I: Future<Output = Result<JsValue, Error>> + Unpin,
+{
+    type Output = Result<JsValue, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.fut).poll(cx).map(|res| {
+            if let (Err(e), Some(f)) = (&res, me.f.take()) {
+                f(e);
+            }
+
+            res
+        })
+    }
+}
+
+/// Represents the future of an [invoke](api::invoke)
+/// operation with a binary result.
+#[must_use = "this does nothing unless awaited"]
+pub struct BytesFuture(InvokeFuture);
+
+impl Future for BytesFuture {
+    type Output = Result<Vec<u8>, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.0).poll(cx).map(|res| {
+            let value = res?;
+            response::to_bytes(&value).ok_or_else(|| {
+                let e = TypeError::new("the invoke result is not binary");
+                Error::js(JsValue::from(e))
+            })
+        })
+    }
+}
+
+/// Represents the future of an [invoke](api::invoke)
+/// operation with a string result.
+#[must_use = "this does nothing unless awaited"]
+pub struct TextFuture(InvokeFuture);
+
+impl Future for TextFuture {
+    type Output = Result<String, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.0).poll(cx).map(|res| {
+            let value = res?;
+            match value.dyn_into::<JsString>() {
+                Ok(s) => Ok(String::from(s)),
+                Err(_) => {
+                    let e = TypeError::new("the invoke result is not a string");
+                    Err(Error::js(JsValue::from(e)))
+                }
+            }
+        })
+    }
+}
+
+/// Represents the future of an [invoke](api::invoke)
+/// operation with a [response](JsResponse) result.
+#[must_use = "this does nothing unless awaited"]
+pub struct ResponseFuture(InvokeFuture);
+
+impl Future for ResponseFuture {
+    type Output = Result<JsResponse, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.0).poll(cx).map_ok(JsResponse::from)
+    }
+}
+
+/// Represents the future of an [invoke](api::invoke)
+/// operation with a deserialized result.
+#[cfg(feature = "serde")]
+#[must_use = "this does nothing unless awaited"]
+pub struct DeserializeFuture<T> {
+    fut: InvokeFuture,
+    ty: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> Future for DeserializeFuture<T>
+where
+    T: DeserializeOwned,
+{
+    type Output = Result<T, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        Pin::new(&mut me.fut).poll(cx).map(|res| {
+            let value = res?;
+            serde_wasm_bindgen::from_value(value).map_err(Error::from)
+        })
+    }
+}
+
+impl<C, A, R> IntoFuture for Invoke<C, A, R>
+where
+    C: AsRef<JsValue>,
+    A: AsRef<JsValue>,
+    R: ResponseKind,
+{
+    type Output = Result<R::Output, Error>;
+    type IntoFuture = R::Future;
+
+    #[inline]
+    fn into_future(self) -> Self::IntoFuture {
+        R::future(self.start())
+    }
+}
+
+impl<C, A, R> Invoke<C, A, R>
+where
+    C: AsRef<JsValue>,
+    A: AsRef<JsValue>,
+{
+    /// Starts the invoke operation with a raw result.
+    fn start(self) -> InvokeFuture {
+        // the progress channel is created only in tauri,
+        // since it registers a callback in the tauri runtime
+        let started = self.args.and_then(|args| {
+            ext::ensure_tauri()?;
+            let progress = self.progress.map(|f| f()).transpose()?;
+            let args = match &progress {
+                Some(channel) => with_progress_arg(args.as_ref(), &channel.value)?,
+                None => args.as_ref().clone(),
+            };
+
+            Ok((args, progress))
+        });
+
+        #[cfg(feature = "tracing")]
+        let trace = Trace::invoke(self.cmd.as_ref());
+
+        let (state, progress) = match started {
+            Ok((args, progress)) => {
+                let promise = ext::invoke(self.cmd.as_ref(), &args, self.opts.non_empty());
+                (State::Pending(JsFuture::from(promise)), progress)
+            }
+            Err(e) => (State::Failed(Some(e)), None),
+        };
+
+        InvokeFuture {
+            state,
+            _progress: progress,
+            timeout: self.timeout.map(Timeout::new),
+            #[cfg(feature = "abort")]
+            abort: self.signal.map(|signal| {
+                let fut = JsFuture::from(ext::aborted(&signal));
+                Abort { signal, fut }
+            }),
+            #[cfg(feature = "tracing")]
+            trace,
+        }
+    }
+}
+
+/// Kinds of results an [invoke](api::invoke) operation can resolve with.
+///
+/// See the [response type](Invoke#response-type) for details.
+pub trait ResponseKind {
+    /// The result of the invoke operation.
+    type Output;
+
+    /// The future resolving with the result.
+    type Future: Future<Output = Result<Self::Output, Error>>;
+
+    /// Converts the invoke future with a raw result.
+    fn future(fut: InvokeFuture) -> Self::Future;
+}
+
+impl ResponseKind for JsValue {
+    type Output = Self;
+    type Future = InvokeFuture;
+
+    #[inline]
+    fn future(fut: InvokeFuture) -> Self::Future {
+        fut
+    }
+}
+
+impl ResponseKind for String {
+    type Output = Self;
+    type Future = TextFuture;
+
+    #[inline]
+    fn future(fut: InvokeFuture) -> Self::Future {
+        fut.text()
+    }
+}
+
+impl ResponseKind for JsResponse {
+    type Output = Self;
+    type Future = ResponseFuture;
+
+    #[inline]
+    fn future(fut: InvokeFuture) -> Self::Future {
+        fut.response()
+    }
+}
+
+impl ResponseKind for Vec<u8> {
+    type Output = Self;
+    type Future = BytesFuture;
+
+    #[inline]
+    fn future(fut: InvokeFuture) -> Self::Future {
+        fut.bytes()
+    }
+}
+
+/// A marker of the deserialized [response type](Invoke#response-type).
+#[cfg(feature = "serde")]
+pub struct Typed<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "serde")]
+impl<T> ResponseKind for Typed<T>
+where
+    T: DeserializeOwned,
+{
+    type Output = T;
+    type Future = DeserializeFuture<T>;
+
+    #[inline]
+    fn future(fut: InvokeFuture) -> Self::Future {
+        fut.deserialize()
+    }
+}
+
+/// Checks the arguments are an object of named parameters
+/// rather than a raw request body.
+fn is_object_args(args: &JsValue) -> bool {
+    args.is_object()
+        && !args.is_instance_of::<ArrayBuffer>()
+        && !args.is_instance_of::<Uint8Array>()
+        && !Array::is_array(args)
+}
+
+fn merge_args(args: &JsValue, other: &JsValue) -> Result<JsValue, Error> {
+    if args.is_undefined() {
+        return Ok(other.clone());
+    }
+
+    if other.is_undefined() {
+        return Ok(args.clone());
+    }
+
+    if !is_object_args(args) || !is_object_args(other) {
+        let e = TypeError::new("only object invoke arguments can be merged");
+        return Err(Error::js(JsValue::from(e)));
+    }
+
+    let obj = Object::assign2(&Object::new(), args.unchecked_ref(), other.unchecked_ref());
+    Ok(JsValue::from(obj))
+}
+
+/// A progress handler of an invoke operation.
+///
+/// The channel is created when the operation starts.
+type Progress = Box<dyn FnOnce() -> Result<ProgressChannel, Error>>;
+
+/// A channel of a started invoke operation.
+struct ProgressChannel {
+    value: JsValue,
+    _channel: Box<dyn Any>,
+}
+
+impl ProgressChannel {
+    fn new<T>(channel: Channel<T>) -> Self
+    where
+        T: 'static,
+    {
+        Self {
+            value: channel.to_value(),
+            _channel: Box::new(channel),
+        }
+    }
+}
+
+fn with_progress_arg(args: &JsValue, channel: &JsValue) -> Result<JsValue, Error> {
+    let obj = Object::new();
+    if !args.is_undefined() {
+        if !is_object_args(args) {
+            let e = TypeError::new("the invoke arguments with progress must be an object");
+            return Err(Error::js(JsValue::from(e)));
+        }
+
+        Object::assign(&obj, args.unchecked_ref());
+    }
+
+    Reflect::set(&obj, &JsValue::from_str("onProgress"), channel).map_err(Error::js)?;
+
+    Ok(JsValue::from(obj))
+}
+
+/// Types that can be represented as arguments.
+///
+/// # Binary arguments
+///
+/// Byte containers like `&[u8]`, [`Vec<u8>`] or [`Box<[u8]>`](Box)
+/// are passed as a [`Uint8Array`]. Note that the conversion
+/// always copies bytes from WASM memory into a new JS array,
+/// even for owned buffers. To avoid the copy of a large buffer,
+/// see the [`args_bytes_view`] function.
+///
+/// Other binary web types like a `Blob` or `FormData` can't be
+/// passed as arguments, since the IPC serializes them as empty
+/// JSON objects. A blob can be read into a buffer first with
+/// the `read_blob` function of the `blob` feature.
+///
+/// # Raw JS values
+///
+/// A [`JsValue`] or an [`Object`] is passed unchanged, so an object
+/// built manually or received from JS code can be used as arguments.
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use js_sys::{Object, Reflect};
+///
+/// let args = Object::new();
+/// Reflect::set(&args, &"name".into(), &"anon".into())?;
+/// tauri_wasm::invoke("greet").with_args(&args).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ToArgs {
+    type Js: AsRef<JsValue>;
+
+    /// Converts the value into arguments.
+    fn to_args(self) -> Result<Self::Js, Error>;
+}
+
+impl ToArgs for JsValue {
+    type Js = Self;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(self)
+    }
+}
+
+impl<'val> ToArgs for &'val JsValue {
+    type Js = &'val JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(self)
+    }
+}
+
+impl ToArgs for Object {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(JsValue::from(self))
+    }
+}
+
+impl<'obj> ToArgs for &'obj Object {
+    type Js = &'obj JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(self)
+    }
+}
+
+impl ToArgs for ArrayBuffer {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(JsValue::from(self))
+    }
+}
+
+impl<'arr> ToArgs for &'arr ArrayBuffer {
+    type Js = &'arr JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(self)
+    }
+}
 
 impl ToArgs for Uint8Array {
     type Js = JsValue;
 
     #[inline]
-    fn to_args(self) -> Self::Js {
-        JsValue::from(self)
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(JsValue::from(self))
     }
 }
 
@@ -239,8 +1766,8 @@ impl<'arr> ToArgs for &'arr Uint8Array {
     type Js = &'arr JsValue;
 
     #[inline]
-    fn to_args(self) -> Self::Js {
-        self
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(self)
     }
 }
 
@@ -248,50 +1775,472 @@ impl ToArgs for &[u8] {
     type Js = JsValue;
 
     #[inline]
-    fn to_args(self) -> Self::Js {
+    fn to_args(self) -> Result<Self::Js, Error> {
         Uint8Array::from(self).to_args()
     }
 }
 
+impl ToArgs for Vec<u8> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        self.as_slice().to_args()
+    }
+}
+
+impl ToArgs for Box<[u8]> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        (*self).to_args()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ToArgs for bytes::Bytes {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        (*self).to_args()
+    }
+}
+
 impl<const N: usize> ToArgs for &[u8; N] {
     type Js = JsValue;
 
     #[inline]
-    fn to_args(self) -> Self::Js {
+    fn to_args(self) -> Result<Self::Js, Error> {
         self.as_slice().to_args()
     }
 }
 
+/// Creates binary arguments viewing the bytes without copying.
+///
+/// Unlike passing a `&[u8]`, which copies the bytes into a new
+/// JS array, the returned arguments refer directly to the WASM
+/// memory. This is useful to send large buffers to a command.
+///
+/// # Safety
+///
+/// The view is valid only while the WASM memory isn't reallocated.
+/// When the memory grows, for example, because of any allocation,
+/// the view is detached and the command receives no bytes or
+/// unrelated data. So no allocations may happen between creating
+/// the view and starting the invoke operation, which copies the
+/// bytes into the IPC request.
+///
+/// Note that when the custom protocol IPC fails, tauri retries the
+/// request with the `postMessage` interface later, so the view
+/// is read again after the operation is started. Make sure the
+/// memory isn't reallocated until the operation is finished
+/// if the app may fall back to that interface.
+///
+/// # Example
+///
+/// ```
+/// # async fn e(data: &[u8]) -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::invoke;
+///
+/// // SAFETY: nothing is allocated until the invoke is started
+/// let args = unsafe { invoke::args_bytes_view(data) };
+/// tauri_wasm::invoke("upload").with_args(args).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub unsafe fn args_bytes_view(data: &[u8]) -> BytesView<'_> {
+    // SAFETY: the caller guarantees the memory isn't reallocated
+    let array = unsafe { Uint8Array::view(data) };
+    BytesView {
+        array: JsValue::from(array),
+        data: PhantomData,
+    }
+}
+
+/// Binary arguments viewing the bytes without copying.
+///
+/// See [`args_bytes_view`] for details.
+#[derive(Debug)]
+pub struct BytesView<'data> {
+    array: JsValue,
+    data: PhantomData<&'data [u8]>,
+}
+
+impl AsRef<JsValue> for BytesView<'_> {
+    #[inline]
+    fn as_ref(&self) -> &JsValue {
+        &self.array
+    }
+}
+
+impl<'data> ToArgs for BytesView<'data> {
+    type Js = Self;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(self)
+    }
+}
+
 /// Invoke options.
 ///
 /// To pass options to an invoke call, use the
 /// [`with_options`](Invoke::with_options) method.
 ///
 /// You can create options from
-/// [headers](IntoHeaders::into_options).
+/// [headers](IntoHeaders::into_options),
+/// with the [builder](Options::builder)
+/// or start with [no headers](Options::empty).
+///
+/// # Headers shape
+///
+/// However the options are created, headers are always stored
+/// as a plain JS object mapping header names to string values.
+/// This is the shape the tauri IPC reads from the `headers` field
+/// and forwards to the backend as request headers. Other shapes,
+/// like a JS `Map`, may be silently dropped on some IPC paths,
+/// so `Headers` values are converted into a plain object too.
+///
+/// Options can be cloned to reuse them across multiple
+/// invoke calls. Note that cloning doesn't copy headers,
+/// the clone refers to the same JS object.
 #[wasm_bindgen]
+#[derive(Clone, Debug)]
 pub struct Options {
     pub(crate) headers: JsValue,
+    pub(crate) response: Option<ResponseType>,
 }
 
 impl Options {
-    pub(crate) const fn empty() -> Self {
+    /// Creates options without headers.
+    ///
+    /// This is the same as passing no options at all, which is
+    /// useful when headers are attached only on some code paths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e(token: Option<&str>) -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::invoke::Options;
+    ///
+    /// let opts = match token {
+    ///     Some(token) => Options::from_record([("token", token)])?,
+    ///     None => Options::empty(),
+    /// };
+    ///
+    /// tauri_wasm::invoke("send").with_options(opts).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn empty() -> Self {
         let headers = JsValue::UNDEFINED;
+        let response = None;
+        Self { headers, response }
+    }
+
+    /// Returns `None` for empty options, so no
+    /// JS object is created to pass them.
+    fn non_empty(self) -> Option<Self> {
+        if self.headers.is_undefined() && self.response.is_none() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Returns the value of the header with the given key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::invoke::Options;
+    ///
+    /// let opts = Options::from_record([("secret", "2")])?;
+    /// assert_eq!(opts.get("secret").as_deref(), Some("2"), "the header should exist");
+    /// assert_eq!(opts.get("data"), None, "the header should not exist");
+    /// assert_eq!(opts.len(), 1, "options should have one header");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<String> {
+        ext::hget(&self.headers, key).as_string()
+    }
+
+    /// Returns the number of headers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        ext::hlen(&self.headers) as usize
+    }
+
+    /// Checks whether there are no headers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Requests the response type.
+    ///
+    /// By default, no response type is requested and it's detected
+    /// automatically: a command responds with an `ArrayBuffer` when
+    /// it returns [`tauri::ipc::Response`] and with a JSON value
+    /// otherwise. This option explicitly requests the type and is
+    /// passed to the invoke call as the `responseType` field.
+    /// The [raw](ResponseType::Raw) type pairs with the
+    /// [`bytes`](InvokeFuture::bytes) function.
+    ///
+    /// Note that the host decides how to handle the field.
+    /// Tauri versions without the response type selection
+    /// ignore it and keep detecting the type automatically.
+    ///
+    /// [`tauri::ipc::Response`]: https://docs.rs/tauri/latest/tauri/ipc/struct.Response.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::invoke::{Options, ResponseType};
+    ///
+    /// let opts = Options::empty().with_response_type(ResponseType::Raw);
+    /// let thumbnail = tauri_wasm::invoke("thumbnail")
+    ///     .with_options(opts)
+    ///     .bytes()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_response_type(self, response: ResponseType) -> Self {
+        let response = Some(response);
+        Self { response, ..self }
+    }
+
+    /// Requests the raw response type.
+    ///
+    /// This is a shortcut for the [`with_response_type`](Options::with_response_type)
+    /// function with the [raw](ResponseType::Raw) type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::invoke::Options;
+    ///
+    /// let opts = Options::builder().build()?.with_response_raw();
+    /// let thumbnail = tauri_wasm::invoke("thumbnail")
+    ///     .with_options(opts)
+    ///     .bytes()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_response_raw(self) -> Self {
+        self.with_response_type(ResponseType::Raw)
+    }
+
+    /// Creates a [builder](OptionsBuilder) of options.
+    ///
+    /// The builder allows to add headers one by one,
+    /// which is useful when headers are computed conditionally.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, tauri_wasm::invoke::Options};
+    ///
+    /// # let token = "secret";
+    /// # let trace = true;
+    /// let mut builder = Options::builder().header("auth", token);
+    /// if trace {
+    ///     builder = builder.header("trace", "1");
+    /// }
+    ///
+    /// let opts = builder.build()?;
+    /// let message = tauri_wasm::invoke("send").with_options(opts).await?;
+    /// console::log!("received from backend", message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn builder() -> OptionsBuilder {
+        let headers = Ok(String::new());
+        OptionsBuilder { headers }
+    }
+
+    /// Creates options from header key-value pairs.
+    ///
+    /// Unlike `from_record`, keys are not required to be static,
+    /// so header names can be computed at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, tauri_wasm::invoke::Options};
+    ///
+    /// let headers: Vec<(String, String)> = (0..3)
+    ///     .map(|n| (format!("app-{n}"), n.to_string()))
+    ///     .collect();
+    ///
+    /// let opts = Options::from_pairs(headers)?;
+    /// let message = tauri_wasm::invoke("send").with_options(opts).await?;
+    /// console::log!("received from backend", message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_pairs<I, K, V>(pairs: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        pairs
+            .into_iter()
+            .fold(Self::builder(), |builder, (key, val)| {
+                builder.header(key, val)
+            })
+            .build()
+    }
+
+    /// Creates options from static header names and values.
+    ///
+    /// The headers object is built directly
+    /// and doesn't require the `serde` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::invoke::Options;
+    ///
+    /// let opts = Options::from_record([("app-token", "secret")])?;
+    /// tauri_wasm::invoke("send").with_options(opts).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_record<'val, I>(fields: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (&'static str, &'val str)>,
+    {
+        Self::from_pairs(fields)
+    }
+}
+
+impl Default for Options {
+    #[inline]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// A builder of invoke [options](Options).
+pub struct OptionsBuilder {
+    // headers are packed into `key\0value\0` pairs and
+    // unpacked into an object with a single JS call
+    headers: Result<String, Error>,
+}
+
+impl OptionsBuilder {
+    /// Adds a header.
+    ///
+    /// If the header with the same key
+    /// is already added, its value is replaced.
+    ///
+    /// A key or a value with a null character is not a valid
+    /// header, so the [`build`](OptionsBuilder::build)
+    /// function returns an [error](Error) then.
+    #[inline]
+    pub fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let headers = self.headers.and_then(|mut headers| {
+            let (key, value) = (key.as_ref(), value.as_ref());
+            if key.contains('\0') || value.contains('\0') {
+                let e = TypeError::new(&format!("header {key} contains a null character"));
+                return Err(Error::js(JsValue::from(e)));
+            }
+
+            headers.reserve(key.len() + value.len() + 2);
+            headers.push_str(key);
+            headers.push('\0');
+            headers.push_str(value);
+            headers.push('\0');
+            Ok(headers)
+        });
+
         Self { headers }
     }
+
+    /// Builds the [options](Options).
+    ///
+    /// Returns an [error](Error) if a header couldn't be added.
+    #[inline]
+    pub fn build(self) -> Result<Options, Error> {
+        let headers = JsValue::from(ext::unpack(&self.headers?));
+        Ok(Options {
+            headers,
+            ..Options::empty()
+        })
+    }
 }
 
+// exported functions can't be inlined
 #[wasm_bindgen]
+#[allow(clippy::missing_inline_in_public_items)]
 impl Options {
     /// Returns options headers.
-    #[inline]
     #[wasm_bindgen(getter)]
-    pub fn headers(self) -> JsValue {
-        self.headers
+    pub fn headers(&self) -> JsValue {
+        self.headers.clone()
+    }
+
+    /// Returns the requested response type.
+    #[wasm_bindgen(getter = responseType)]
+    pub fn response_type(&self) -> Option<String> {
+        self.response.map(|response| response.as_str().to_owned())
+    }
+}
+
+/// The response type of an invoke operation.
+///
+/// See [`with_response_type`](Options::with_response_type) for details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponseType {
+    /// A JSON value.
+    Json,
+
+    /// Raw binary data.
+    Raw,
+}
+
+impl ResponseType {
+    /// Returns the name of the response type
+    /// passed to the invoke call.
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Raw => "raw",
+        }
     }
 }
 
 /// Types that can be converted into headers.
+///
+/// Implementations must return a plain JS object mapping
+/// header names to string values, see the
+/// [headers shape](Options#headers-shape) for details.
 pub trait IntoHeaders {
     /// Converts the value into headers.
     fn into_headers(self) -> Result<JsValue, Error>;
@@ -303,6 +2252,9 @@ pub trait IntoHeaders {
         Self: Sized,
     {
         let headers = self.into_headers()?;
-        Ok(Options { headers })
+        Ok(Options {
+            headers,
+            ..Options::empty()
+        })
     }
 }
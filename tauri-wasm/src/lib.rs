@@ -2,22 +2,62 @@
 #![cfg_attr(all(doc, not(doctest)), doc = include_str!("../README.md"))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-mod error;
+mod app;
+#[cfg(feature = "blob")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blob")))]
+mod blob;
+mod channel;
+#[cfg(feature = "clipboard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+pub mod clipboard;
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+mod command;
+mod error;
 pub mod event;
 mod ext;
 #[cfg(feature = "headers")]
 #[cfg_attr(docsrs, doc(cfg(feature = "headers")))]
 mod headers;
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+pub mod http;
 pub mod invoke;
+#[cfg(feature = "mock")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock")))]
+pub mod mock;
+#[cfg(feature = "process")]
+#[cfg_attr(docsrs, doc(cfg(feature = "process")))]
+pub mod process;
+mod response;
+mod retry;
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 mod serde;
 mod string;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod window;
 
-pub use crate::{error::Error, ext::is_tauri, invoke::api::invoke, string::ToStringValue};
+pub use crate::{
+    app::{app_name, app_version, core_version},
+    error::{Error, ErrorKind},
+    event::{
+        Emit, EventTarget,
+        api::{emit_raw, emit_str},
+    },
+    ext::{
+        convert_file_src, is_tauri, is_tauri_cached, reset_invoke_fn, set_invoke_fn,
+        transform_callback,
+    },
+    invoke::api::invoke,
+    string::ToStringValue,
+    window::current_window_label,
+};
 
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
-pub use crate::{event::api::emit, serde::args};
+pub use crate::{
+    event::api::{emit, emit_any, emit_detached},
+    serde::{args, args_with, from_value, named_args},
+};
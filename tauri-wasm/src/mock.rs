@@ -0,0 +1,208 @@
+//! Mocks of the tauri IPC for testing.
+//!
+//! This is useful to test frontend code that invokes commands
+//! without a running tauri backend, for example, with
+//! `wasm-pack test` in a headless browser. It works similar to
+//! [`mockIPC`] from the `@tauri-apps/api` package.
+//!
+//! [`mockIPC`]: https://v2.tauri.app/reference/javascript/api/namespacemocks/#mockipc
+//!
+//! The module is available only with the `mock` feature,
+//! so it's usually enabled for dev-dependencies:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! tauri-wasm = { version = "*", features = ["mock"] }
+//! ```
+//!
+//! A mock handler replaces the `invoke` function of the
+//! `window.__TAURI_INTERNALS__` object and marks the environment
+//! as tauri, so [`is_tauri`](crate::is_tauri) returns `true`.
+//! Note that the result of [`is_tauri_cached`](crate::is_tauri_cached)
+//! doesn't change if it was checked before the mock was installed.
+//!
+//! Handlers can be registered for specific commands with the
+//! [`on`] function or for all commands with the [`set_handler`]
+//! function. Invoking a command without a handler is rejected with
+//! the same message tauri uses for unregistered commands, so it can
+//! be checked with [`is_command_not_found`](crate::Error::is_command_not_found).
+//! Call [`clear`] to remove all handlers between tests.
+//!
+//! The mock is meant for test environments where tauri doesn't
+//! inject its global API. Otherwise, the `window.__TAURI__` object
+//! takes precedence and invoke calls aren't mocked.
+//!
+//! # Events
+//!
+//! Event functions are built on the `plugin:event|*` commands,
+//! so they are mocked the same way. The event plugin internals of
+//! tauri don't exist in the mocked environment, but listeners can
+//! still be registered and dropped:
+//!
+//! ```
+//! # async fn e() -> Result<(), tauri_wasm::Error> {
+//! use {
+//!     tauri_wasm::{event, mock},
+//!     wasm_bindgen::JsValue,
+//! };
+//!
+//! mock::on("plugin:event|listen", |_| Ok(JsValue::from(1)));
+//! mock::on("plugin:event|unlisten", |_| Ok(JsValue::UNDEFINED));
+//!
+//! let unlisten = event::listen("ping", |_| {}).await?;
+//! assert_eq!(unlisten.id(), 1);
+//!
+//! // unregisters the listener with the mocked command
+//! drop(unlisten);
+//!
+//! // a malformed listener id is an error
+//! mock::on("plugin:event|listen", |_| Ok(JsValue::from("1")));
+//! assert!(event::listen("ping", |_| {}).await.is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use {
+    crate::ext,
+    std::{cell::RefCell, collections::HashMap},
+    wasm_bindgen::prelude::*,
+};
+
+type Handler = Box<dyn FnMut(&str, JsValue) -> Result<JsValue, JsValue>>;
+type CommandHandler = Box<dyn FnMut(JsValue) -> Result<JsValue, JsValue>>;
+type Dispatch = Closure<dyn FnMut(String, JsValue) -> Result<JsValue, JsValue>>;
+
+#[derive(Default)]
+struct Mock {
+    handler: Option<Handler>,
+    commands: HashMap<String, CommandHandler>,
+    generation: u32,
+}
+
+thread_local! {
+    static MOCK: RefCell<Mock> = RefCell::default();
+    static DISPATCH: Dispatch = Dispatch::new(dispatch);
+}
+
+fn install() {
+    DISPATCH.with(|f| ext::mock(f.as_ref()));
+}
+
+fn dispatch(cmd: String, args: JsValue) -> Result<JsValue, JsValue> {
+    // handlers are taken out during the call, so they can
+    // register other handlers or invoke commands themselves
+    let (command, generation) = MOCK.with(|m| {
+        let mut m = m.borrow_mut();
+        (m.commands.remove_entry(&cmd), m.generation)
+    });
+
+    if let Some((cmd, mut f)) = command {
+        let res = f(args);
+        restore(generation, |m| _ = m.commands.entry(cmd).or_insert(f));
+        return res;
+    }
+
+    let handler = MOCK.with(|m| m.borrow_mut().handler.take());
+    if let Some(mut f) = handler {
+        let res = f(&cmd, args);
+        restore(generation, |m| _ = m.handler.get_or_insert(f));
+        return res;
+    }
+
+    Err(JsValue::from(format!("command {cmd} not found")))
+}
+
+/// Puts a handler back unless it was replaced or
+/// the mock was [cleared](clear) during the call.
+fn restore<F>(generation: u32, f: F)
+where
+    F: FnOnce(&mut Mock),
+{
+    MOCK.with(|m| {
+        let mut m = m.borrow_mut();
+        if m.generation == generation {
+            f(&mut m);
+        }
+    });
+}
+
+/// Sets the handler of all invoke calls.
+///
+/// The handler receives the command name and its arguments.
+/// If it returns `Ok`, the invoke call resolves with the value.
+/// Otherwise, the call is rejected with the error value, the
+/// same way a backend command returning `Err` is rejected.
+///
+/// Commands registered with [`on`] take precedence over
+/// this handler. The new handler replaces the previous one.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {tauri_wasm::mock, wasm_bindgen::JsValue};
+///
+/// mock::set_handler(|cmd, args| match cmd {
+///     "echo" => Ok(args),
+///     _ => Err(JsValue::from("unknown")),
+/// });
+///
+/// let message = tauri_wasm::invoke("echo").with_args(JsValue::from("hi")).await?;
+/// assert_eq!(message, "hi");
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn set_handler<F>(f: F)
+where
+    F: FnMut(&str, JsValue) -> Result<JsValue, JsValue> + 'static,
+{
+    MOCK.with(|m| m.borrow_mut().handler = Some(Box::new(f)));
+    install();
+}
+
+/// Sets the handler of the command.
+///
+/// The handler receives the command arguments and works
+/// the same way as the one passed to [`set_handler`].
+/// The new handler replaces the previous one
+/// registered for the same command.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {tauri_wasm::mock, wasm_bindgen::JsValue};
+///
+/// mock::on("greet", |_| Ok(JsValue::from("hi")));
+///
+/// let message = tauri_wasm::invoke("greet").await?;
+/// assert_eq!(message, "hi");
+///
+/// let e = tauri_wasm::invoke("login").await.unwrap_err();
+/// assert!(e.is_command_not_found());
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn on<F>(cmd: &str, f: F)
+where
+    F: FnMut(JsValue) -> Result<JsValue, JsValue> + 'static,
+{
+    MOCK.with(|m| m.borrow_mut().commands.insert(cmd.to_owned(), Box::new(f)));
+    install();
+}
+
+/// Removes all handlers.
+///
+/// After this call, any invoked command is rejected
+/// as unregistered until a new handler is set.
+#[inline]
+pub fn clear() {
+    MOCK.with(|m| {
+        let mut m = m.borrow_mut();
+        m.handler = None;
+        m.commands.clear();
+        m.generation = m.generation.wrapping_add(1);
+    });
+}
@@ -0,0 +1,54 @@
+//! Bindings of the [process plugin].
+//!
+//! [process plugin]: https://v2.tauri.app/plugin/process/
+//!
+//! # Capabilities
+//!
+//! The plugin should be installed on the backend and
+//! the `process:default` permission should be enabled.
+//! For example, your `Tauri.toml` config should include
+//! something like:
+//!
+//! ```toml
+//! [[app.security.capabilities]]
+//! identifier = "default"
+//! windows = ["app"]
+//! permissions = ["process:default"]
+//! ```
+
+use crate::{error::Error, ext, invoke::api::invoke};
+
+/// Exits the application with the status code.
+///
+/// Note that the backend process terminates while
+/// handling the command, so the returned future
+/// may never resolve. Don't rely on code after it.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::process;
+///
+/// process::exit(0).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn exit(code: i32) -> Result<(), Error> {
+    invoke("plugin:process|exit")
+        .with_args(ext::pargs(code))
+        .await?;
+
+    Ok(())
+}
+
+/// Restarts the application.
+///
+/// Like [`exit`], the returned future may never
+/// resolve, since the current process terminates.
+#[inline]
+pub async fn relaunch() -> Result<(), Error> {
+    invoke("plugin:process|restart").await?;
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use {
+    js_sys::{ArrayBuffer, Uint8Array},
+    wasm_bindgen::prelude::*,
+};
+
+#[cfg(feature = "serde")]
+use {crate::error::Error, serde::de::DeserializeOwned};
+
+/// A result of an invoke operation.
+///
+/// The response wraps a raw JS value and provides typed
+/// accessors for it. This is useful when a command can
+/// respond with values of different types, so the result
+/// can be inspected without committing to a specific type
+/// in advance. The raw value is still accessible with the
+/// [`into_inner`](JsResponse::into_inner) method.
+///
+/// An invoke operation resolves with the response directly
+/// when it's set as the [response type](crate::invoke::Invoke#response-type)
+/// or with the [`response`](crate::invoke::Invoke::response) function.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::invoke::JsResponse};
+///
+/// let res = tauri_wasm::invoke("status").returning::<JsResponse>().await?;
+/// if let Some(status) = res.as_str() {
+///     console::log!("status", status);
+/// } else if let Some(code) = res.as_f64() {
+///     console::log!("status code", code);
+/// } else if res.is_null() {
+///     console::log!("no status");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct JsResponse(JsValue);
+
+impl JsResponse {
+    /// Returns the response as a string
+    /// if it is a JS string.
+    #[inline]
+    pub fn as_str(&self) -> Option<String> {
+        self.0.as_string()
+    }
+
+    /// Returns the response as a number
+    /// if it is a JS number.
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        self.0.as_f64()
+    }
+
+    /// Returns the response as a boolean
+    /// if it is a JS boolean.
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        self.0.as_bool()
+    }
+
+    /// Returns a copy of the response bytes if it is
+    /// an [`ArrayBuffer`] or a [`Uint8Array`].
+    #[inline]
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        to_bytes(&self.0)
+    }
+
+    /// Checks if the response is `null` or `undefined`.
+    ///
+    /// A command returning `()` or `None`
+    /// responds with one of these values.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.0.is_null() || self.0.is_undefined()
+    }
+
+    /// Deserializes the response.
+    ///
+    /// See [`from_value`](crate::from_value) for details.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    #[inline]
+    pub fn deserialize<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        crate::serde::from_value(&self.0)
+    }
+
+    /// Returns the raw JS value.
+    #[inline]
+    pub fn into_inner(self) -> JsValue {
+        self.0
+    }
+}
+
+impl From<JsValue> for JsResponse {
+    #[inline]
+    fn from(value: JsValue) -> Self {
+        Self(value)
+    }
+}
+
+impl AsRef<JsValue> for JsResponse {
+    #[inline]
+    fn as_ref(&self) -> &JsValue {
+        &self.0
+    }
+}
+
+pub(crate) fn to_bytes(value: &JsValue) -> Option<Vec<u8>> {
+    if let Some(buf) = value.dyn_ref::<ArrayBuffer>() {
+        Some(Uint8Array::new(buf).to_vec())
+    } else {
+        value.dyn_ref::<Uint8Array>().map(Uint8Array::to_vec)
+    }
+}
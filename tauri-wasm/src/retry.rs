@@ -0,0 +1,152 @@
+use {
+    crate::{error::Error, invoke::Timeout},
+    std::{future, time::Duration},
+};
+
+/// A policy to [retry] failed operations.
+///
+/// The policy limits the number of attempts, sets the delay
+/// between them and decides which errors should be retried.
+#[derive(Clone, Copy, Debug)]
+pub struct Retry<P = fn(&Error) -> bool> {
+    attempts: u32,
+    delay: Duration,
+    backoff: u32,
+    predicate: P,
+}
+
+impl Retry {
+    /// Creates a policy with the maximum number of attempts.
+    ///
+    /// The operation is always attempted at least once.
+    /// By default, there is no delay between attempts
+    /// and all errors are retried except the ones that
+    /// can't be fixed by retrying: a
+    /// [missing command](Error::is_command_not_found),
+    /// a [denied permission](Error::is_permission_denied),
+    /// a [missing tauri environment](Error::is_not_tauri)
+    /// and a cancelled operation.
+    #[inline]
+    pub fn new(attempts: u32) -> Self {
+        Self {
+            attempts,
+            delay: Duration::ZERO,
+            backoff: 1,
+            predicate: is_transient,
+        }
+    }
+}
+
+impl<P> Retry<P> {
+    /// Sets the delay before the second attempt.
+    #[inline]
+    pub fn with_delay(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// Sets the factor the delay is multiplied by
+    /// after each failed attempt.
+    ///
+    /// The default factor is `1`, so the delay stays the same.
+    /// For example, with a delay of 100 ms and a factor
+    /// of `2`, attempts are delayed by 100, 200, 400 ms
+    /// and so on.
+    #[inline]
+    pub fn with_backoff(self, factor: u32) -> Self {
+        Self {
+            backoff: factor,
+            ..self
+        }
+    }
+
+    /// Retries only the errors matching the predicate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use tauri_wasm::invoke::{self, Retry};
+    ///
+    /// let policy = Retry::new(3).retry_if(|e| e.is_backend());
+    /// invoke::retry(|| tauri_wasm::invoke("sync"), policy).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn retry_if<Q>(self, predicate: Q) -> Retry<Q>
+    where
+        Q: FnMut(&Error) -> bool,
+    {
+        Retry {
+            attempts: self.attempts,
+            delay: self.delay,
+            backoff: self.backoff,
+            predicate,
+        }
+    }
+}
+
+fn is_transient(e: &Error) -> bool {
+    #[cfg(feature = "abort")]
+    if e.is_cancelled() {
+        return false;
+    }
+
+    !e.is_command_not_found() && !e.is_permission_denied() && !e.is_not_tauri()
+}
+
+/// Retries a failed operation according to the [policy](Retry).
+///
+/// The function is called to create the operation
+/// for every attempt, so it can be an [invoke](crate::invoke())
+/// call or any other future resolving with an [error](Error).
+/// If all attempts fail or an error shouldn't be retried,
+/// the last error is returned.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {
+///     gloo::console,
+///     std::time::Duration,
+///     tauri_wasm::invoke::{self, Retry},
+/// };
+///
+/// let policy = Retry::new(5)
+///     .with_delay(Duration::from_millis(100))
+///     .with_backoff(2);
+///
+/// let rates = invoke::retry(|| tauri_wasm::invoke("rates"), policy).await?;
+/// console::log!("received rates", rates);
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn retry<F, I, T, P>(mut f: F, mut policy: Retry<P>) -> Result<T, Error>
+where
+    F: FnMut() -> I,
+    I: IntoFuture<Output = Result<T, Error>>,
+    P: FnMut(&Error) -> bool,
+{
+    let mut delay = policy.delay;
+    let mut attempt = 1;
+    loop {
+        let e = match f().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        if attempt >= policy.attempts || !(policy.predicate)(&e) {
+            return Err(e);
+        }
+
+        if !delay.is_zero() {
+            let mut timer = Timeout::new(delay);
+            future::poll_fn(|cx| timer.poll(cx).map(drop)).await;
+        }
+
+        attempt += 1;
+        delay = delay.saturating_mul(policy.backoff);
+    }
+}
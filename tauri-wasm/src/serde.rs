@@ -3,16 +3,26 @@ use {
         error::Error,
         invoke::{Options, ToArgs},
     },
-    serde::{Serialize, Serializer as _, ser},
+    js_sys::{Array, Object, TypeError},
+    serde::{Serialize, Serializer as _, de::DeserializeOwned, ser},
     serde_wasm_bindgen::Serializer,
     std::collections::HashMap,
-    wasm_bindgen::JsValue,
+    wasm_bindgen::{JsCast, JsValue},
 };
 
+/// The serializer of invoke arguments.
+///
+/// Tauri sends arguments to the backend as JSON, where a JS `Map`
+/// becomes an empty object, so maps are serialized as objects.
+const ARGS: Serializer = Serializer::new().serialize_maps_as_objects(true);
+
 /// Arbitrary serializable data for
 /// [`with_args`](crate::invoke::Invoke::with_args) function.
 ///
-/// Returns an [error](Error) if serialization fails.
+/// Returns an [error](Error) if serialization fails
+/// or the value isn't serialized into an object.
+/// This is a shortcut for the [`Json::new`] function,
+/// see its documentation for details.
 ///
 /// # Example
 ///
@@ -33,25 +43,248 @@ use {
 /// # }
 /// ```
 #[inline]
-pub fn args<T>(args: &T) -> Result<impl ToArgs, Error>
+pub fn args<T>(args: &T) -> Result<Json, Error>
 where
     T: Serialize + ?Sized,
 {
-    struct Data(JsValue);
+    Json::new(args)
+}
 
-    impl ToArgs for Data {
-        type Js = JsValue;
+/// Arbitrary serializable data for
+/// [`with_args`](crate::invoke::Invoke::with_args) function
+/// with a custom serializer.
+///
+/// This is like the [`args`] function, but the value is serialized
+/// with the given [`Serializer`] configuration. This is a shortcut
+/// for the [`Json::with_serializer`] function, see its
+/// documentation for details.
+///
+/// # Example
+///
+/// Pass large integers to the backend as `BigInt` values.
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {serde_wasm_bindgen::Serializer, std::collections::HashMap};
+///
+/// let data = HashMap::from([("id", u64::MAX)]);
+///
+/// let ser = Serializer::new()
+///     .serialize_maps_as_objects(true)
+///     .serialize_large_number_types_as_bigints(true);
+///
+/// let args = tauri_wasm::args_with(&data, &ser)?;
+/// tauri_wasm::invoke("remove").with_args(args).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn args_with<T>(args: &T, ser: &Serializer) -> Result<Json, Error>
+where
+    T: Serialize + ?Sized,
+{
+    Json::with_serializer(args, ser)
+}
 
-        fn to_args(self) -> Self::Js {
-            self.0
+/// Arguments of a single named parameter for
+/// [`with_args`](crate::invoke::Invoke::with_args) function.
+///
+/// The value is serialized into an object with one field
+/// `{ key: value }`, so it matches a command with the single
+/// parameter named `key`. Note that tauri expects parameter
+/// names in camelCase by default.
+///
+/// Returns an [error](Error) if serialization fails.
+///
+/// # Example
+///
+/// Assume the backend command takes one parameter:
+///
+/// ```ignore
+/// #[tauri::command]
+/// fn greet(name: String) -> String {
+///     format!("hello, {name}!")
+/// }
+/// ```
+///
+/// Then you can pass it without a dedicated struct:
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use gloo::console;
+///
+/// let args = tauri_wasm::named_args("name", &"anon")?;
+/// let message = tauri_wasm::invoke("greet").with_args(args).await?;
+/// console::log!("greeted by backend", message);
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn named_args<T>(key: &'static str, value: &T) -> Result<Json, Error>
+where
+    T: Serialize + ?Sized,
+{
+    use ser::SerializeStruct;
+
+    let mut s = ARGS.serialize_struct("Args", 1)?;
+    s.serialize_field(key, value)?;
+    let data = s.end()?;
+    Ok(Json(data))
+}
+
+/// Deserializes a JS value into a typed value.
+///
+/// Returns an [error](Error) if deserialization fails.
+/// This is the counterpart of the [`args`] function,
+/// useful for values received outside of the typed API,
+/// for example, raw event payloads.
+///
+/// The `null` and `undefined` values are deserialized
+/// into `()` or into an [`Option`] which is `None` then.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {serde::Deserialize, tauri_wasm::event};
+///
+/// #[derive(Deserialize)]
+/// struct Progress {
+///     done: u32,
+///     total: u32,
+/// }
+///
+/// let ev = event::once("progress").await?;
+/// let progress: Progress = tauri_wasm::from_value(ev.payload())?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn from_value<T>(value: &JsValue) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    serde_wasm_bindgen::from_value(value.clone()).map_err(Error::from)
+}
+
+/// Serialized arguments.
+///
+/// The value is serialized in advance when the arguments
+/// are created. So any serialization error is returned from
+/// the [`new`](Json::new) function before the invoke call.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {serde::Serialize, tauri_wasm::invoke::Json};
+///
+/// #[derive(Serialize)]
+/// struct User<'str> {
+///     name: &'str str,
+///     pass: &'str str,
+/// }
+///
+/// let user = User {
+///     name: "anon",
+///     pass: "p@$$w0rD",
+/// };
+///
+/// let args = Json::new(&user)?;
+/// tauri_wasm::invoke("login").with_args(args).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Json(JsValue);
+
+impl Json {
+    /// Serializes the value into arguments.
+    ///
+    /// Returns an [error](Error) if serialization fails.
+    ///
+    /// # Non-object values
+    ///
+    /// Tauri passes arguments to a command by parameter names,
+    /// so the value must serialize into an object like a struct
+    /// or a map, where each field is a command parameter.
+    /// A bare value like a number, a string or a sequence
+    /// cannot be matched to parameters, so this function
+    /// returns an [error](Error) for such values. To pass
+    /// a single value, use the [`named_args`] function. The unit `()`
+    /// is allowed and means no arguments. Serialized bytes
+    /// are also allowed and passed as a raw request body.
+    ///
+    /// # Maps
+    ///
+    /// Maps like [`HashMap`] are serialized as plain JS objects,
+    /// the same way `@tauri-apps/api` passes them, so the backend
+    /// can deserialize them into a struct or a map. To serialize
+    /// them differently, use the [`with_serializer`](Json::with_serializer)
+    /// function.
+    #[inline]
+    pub fn new<T>(value: &T) -> Result<Self, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Self::with_serializer(value, &ARGS)
+    }
+
+    /// Serializes the value into arguments with a custom serializer.
+    ///
+    /// Returns an [error](Error) if serialization fails.
+    ///
+    /// The [`Serializer`] configuration controls how some Rust
+    /// values are represented in JS. Note that tauri sends
+    /// arguments to the backend as JSON, so the serialized value
+    /// should be JSON compatible. For example, a JS `Map` is
+    /// converted to an empty object, so maps should be serialized
+    /// as plain objects with the
+    /// [`serialize_maps_as_objects`](Serializer::serialize_maps_as_objects)
+    /// option. The same [restrictions](Json::new)
+    /// on non-object values apply.
+    #[inline]
+    pub fn with_serializer<T>(value: &T, ser: &Serializer) -> Result<Self, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let data = value.serialize(ser)?;
+        let is_args = data.is_undefined() || (data.is_object() && !Array::is_array(&data));
+        if !is_args {
+            let e = TypeError::new("the invoke arguments must be an object");
+            return Err(Error::js(JsValue::from(e)));
         }
+
+        Ok(Self(data))
     }
+}
 
-    let data = serde_wasm_bindgen::to_value(args).map_err(|e| Error(JsValue::from(e)))?;
-    Ok(Data(data))
+impl ToArgs for Json {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_args(self) -> Result<Self::Js, Error> {
+        Ok(self.0)
+    }
 }
 
 impl Options {
+    /// Creates options from a map of headers.
+    ///
+    /// Returns an [error](Error) if serialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {std::collections::HashMap, tauri_wasm::invoke::Options};
+    ///
+    /// let headers = HashMap::from([("app-token", "secret")]);
+    /// let opts = Options::from_map(&headers)?;
+    /// tauri_wasm::invoke("send").with_options(opts).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
     pub fn from_map<K, V>(map: &HashMap<K, V>) -> Result<Self, Error>
     where
@@ -60,40 +293,90 @@ impl Options {
     {
         use ser::SerializeMap;
 
-        let error = |e| Error(JsValue::from(e));
-
-        let ser = Serializer::new();
-        let mut s = ser.serialize_map(Some(map.len())).map_err(error)?;
+        let ser = Serializer::new().serialize_maps_as_objects(true);
+        let mut s = ser.serialize_map(Some(map.len()))?;
 
         for (key, val) in map {
-            s.serialize_entry(key.as_ref(), val.as_ref())
-                .map_err(error)?;
+            s.serialize_entry(key.as_ref(), val.as_ref())?;
         }
 
-        let headers = s.end().map_err(error)?;
-        Ok(Self { headers })
+        let headers = s.end()?;
+        Ok(Self {
+            headers,
+            ..Self::empty()
+        })
     }
 
+    /// Creates options from a serializable value.
+    ///
+    /// Each field of the value becomes a header.
+    /// String fields are passed as is, numeric and boolean
+    /// fields are converted to strings and `None` fields
+    /// are skipped.
+    ///
+    /// Returns an [error](Error) if the value doesn't serialize
+    /// to an object or some field has an unsupported value,
+    /// like a nested object or an array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn e() -> Result<(), tauri_wasm::Error> {
+    /// use {gloo::console, serde::Serialize, tauri_wasm::invoke::Options};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Headers<'str> {
+    ///     auth: &'str str,
+    ///     retries: u32,
+    /// }
+    ///
+    /// let headers = Headers {
+    ///     auth: "token",
+    ///     retries: 3,
+    /// };
+    ///
+    /// // the "retries" header is sent as "3"
+    /// let opts = Options::from_serialize(&headers)?;
+    /// let message = tauri_wasm::invoke("send").with_options(opts).await?;
+    /// console::log!("received from backend", message);
+    /// # Ok(())
+    /// # }
+    /// ```
     #[inline]
-    pub fn from_record<'val, I>(fields: I) -> Result<Self, Error>
+    pub fn from_serialize<T>(value: &T) -> Result<Self, Error>
     where
-        I: IntoIterator<IntoIter: ExactSizeIterator, Item = (&'static str, &'val str)>,
+        T: Serialize + ?Sized,
     {
-        use ser::SerializeStruct;
+        let ser = Serializer::new().serialize_maps_as_objects(true);
+        let value = value.serialize(&ser)?;
+
+        let Some(obj) = value.dyn_ref::<Object>().filter(|_| !value.is_array()) else {
+            let e = TypeError::new("headers value should serialize to an object");
+            return Err(Error::js(JsValue::from(e)));
+        };
 
-        let fields = fields.into_iter();
-        let error = |e| Error(JsValue::from(e));
+        let mut builder = Self::builder();
+        for entry in Object::entries(obj) {
+            let entry = entry.unchecked_into::<Array>();
+            let key = entry.get(0).as_string().unwrap_or_default();
+            let val = entry.get(1);
 
-        let ser = Serializer::new();
-        let mut s = ser
-            .serialize_struct("Record", fields.len())
-            .map_err(error)?;
+            let val = if let Some(s) = val.as_string() {
+                s
+            } else if let Some(n) = val.as_f64() {
+                n.to_string()
+            } else if let Some(b) = val.as_bool() {
+                b.to_string()
+            } else if val.is_null() || val.is_undefined() {
+                continue;
+            } else {
+                let e = TypeError::new(&format!("header {key} has unsupported value"));
+                return Err(Error::js(JsValue::from(e)));
+            };
 
-        for (key, val) in fields {
-            s.serialize_field(key, val).map_err(error)?;
+            builder = builder.header(key, val);
         }
 
-        let headers = s.end().map_err(error)?;
-        Ok(Self { headers })
+        builder.build()
     }
 }
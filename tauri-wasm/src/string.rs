@@ -1,4 +1,8 @@
-use {js_sys::JsString, wasm_bindgen::JsValue};
+use {
+    js_sys::JsString,
+    std::{borrow::Cow, rc::Rc, sync::Arc},
+    wasm_bindgen::JsValue,
+};
 
 /// A value that can be represented as a JS string.
 pub trait ToStringValue {
@@ -50,3 +54,84 @@ impl ToStringValue for &str {
         JsValue::from(self)
     }
 }
+
+impl ToStringValue for &&str {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        JsValue::from(*self)
+    }
+}
+
+impl ToStringValue for Cow<'_, str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        (&self).to_string_value()
+    }
+}
+
+impl ToStringValue for &Cow<'_, str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        JsValue::from(&**self)
+    }
+}
+
+impl ToStringValue for Box<str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        (&self).to_string_value()
+    }
+}
+
+impl ToStringValue for &Box<str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        JsValue::from(&**self)
+    }
+}
+
+impl ToStringValue for Rc<str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        (&self).to_string_value()
+    }
+}
+
+impl ToStringValue for &Rc<str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        JsValue::from(&**self)
+    }
+}
+
+impl ToStringValue for Arc<str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        (&self).to_string_value()
+    }
+}
+
+impl ToStringValue for &Arc<str> {
+    type Js = JsValue;
+
+    #[inline]
+    fn to_string_value(self) -> Self::Js {
+        JsValue::from(&**self)
+    }
+}
@@ -0,0 +1,54 @@
+use {
+    crate::{error::Error, ext},
+    tracing::{Span, field},
+    wasm_bindgen::JsValue,
+};
+
+/// A span of an IPC operation.
+pub(crate) struct Trace {
+    span: Span,
+    start: f64,
+}
+
+impl Trace {
+    pub(crate) fn invoke(cmd: &JsValue) -> Self {
+        let command = cmd.as_string().unwrap_or_default();
+        let span = tracing::debug_span!(
+            "invoke",
+            command,
+            duration_ms = field::Empty,
+            ok = field::Empty,
+        );
+
+        Self::new(span)
+    }
+
+    pub(crate) fn emit(event: &JsValue) -> Self {
+        let event = event.as_string().unwrap_or_default();
+        let span =
+            tracing::debug_span!("emit", event, duration_ms = field::Empty, ok = field::Empty,);
+
+        Self::new(span)
+    }
+
+    fn new(span: Span) -> Self {
+        let start = ext::now();
+        Self { span, start }
+    }
+
+    pub(crate) fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub(crate) fn finish<T>(&self, res: &Result<T, Error>) {
+        let duration_ms = ext::now() - self.start;
+        self.span.record("duration_ms", duration_ms);
+        self.span.record("ok", res.is_ok());
+
+        let _enter = self.span.enter();
+        match res {
+            Ok(_) => tracing::debug!(duration_ms, "completed"),
+            Err(e) => tracing::debug!(duration_ms, error = %e, "failed"),
+        }
+    }
+}
@@ -0,0 +1,552 @@
+//! Control of application windows.
+//!
+//! The functions are built on the core window commands.
+//! Each [window](Window) is identified by its label.
+//!
+//! # Capabilities
+//!
+//! The `core:window:default` permission only allows
+//! queries like [`is_maximized`](Window::is_maximized).
+//! Commands that change the window should be allowed
+//! explicitly. For example, your `Tauri.toml` config
+//! should include something like:
+//!
+//! ```toml
+//! [[app.security.capabilities]]
+//! identifier = "default"
+//! windows = ["app"]
+//! permissions = [
+//!     "core:window:default",
+//!     "core:window:allow-minimize",
+//!     "core:window:allow-maximize",
+//!     "core:window:allow-close",
+//!     "core:window:allow-destroy",
+//!     "core:window:allow-set-title",
+//! ]
+//! ```
+
+use {
+    crate::{
+        error::Error,
+        event::{self, EventTarget, Unlisten},
+        ext,
+        invoke::api::invoke,
+        string::ToStringValue,
+    },
+    js_sys::{Array, Reflect, TypeError},
+    wasm_bindgen::{JsCast, JsValue},
+};
+
+/// Returns the label of the current window.
+///
+/// Returns `None` if tauri environment is not detected.
+/// This is useful to target events to this window
+/// without hard-coding its label.
+///
+/// # Example
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::event::EventTarget;
+///
+/// if let Some(label) = tauri_wasm::current_window_label() {
+///     let target = EventTarget::window(label);
+///     tauri_wasm::emit("reload", &())?.to(target).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn current_window_label() -> Option<String> {
+    ext::current_label()
+}
+
+/// A handle of the window with the given label.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use tauri_wasm::window::Window;
+///
+/// let window = Window::new("app");
+/// window.set_title("Editor").await?;
+/// if !window.is_maximized().await? {
+///     window.maximize().await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Window<L = JsValue> {
+    label: L,
+}
+
+impl Window {
+    /// Creates a handle of the current window.
+    ///
+    /// Returns `None` if tauri environment is not detected.
+    /// See [`current_window_label`] for details.
+    #[inline]
+    pub fn current() -> Option<Self> {
+        current_window_label().map(Window::new)
+    }
+}
+
+impl<L> Window<L>
+where
+    L: ToStringValue,
+{
+    /// Creates a handle of the window with the label.
+    #[inline]
+    pub fn new(label: L) -> Window<L::Js> {
+        let label = label.to_string_value();
+        Window { label }
+    }
+}
+
+impl<L> Window<L>
+where
+    L: AsRef<JsValue>,
+{
+    /// Returns the window label.
+    #[inline]
+    pub fn label(&self) -> &JsValue {
+        self.label.as_ref()
+    }
+
+    /// Minimizes the window.
+    #[inline]
+    pub async fn minimize(&self) -> Result<(), Error> {
+        self.call("plugin:window|minimize").await
+    }
+
+    /// Restores the window from minimized state.
+    #[inline]
+    pub async fn unminimize(&self) -> Result<(), Error> {
+        self.call("plugin:window|unminimize").await
+    }
+
+    /// Maximizes the window.
+    #[inline]
+    pub async fn maximize(&self) -> Result<(), Error> {
+        self.call("plugin:window|maximize").await
+    }
+
+    /// Restores the window from maximized state.
+    #[inline]
+    pub async fn unmaximize(&self) -> Result<(), Error> {
+        self.call("plugin:window|unmaximize").await
+    }
+
+    /// Toggles the maximized state of the window.
+    #[inline]
+    pub async fn toggle_maximize(&self) -> Result<(), Error> {
+        self.call("plugin:window|toggle_maximize").await
+    }
+
+    /// Closes the window.
+    #[inline]
+    pub async fn close(&self) -> Result<(), Error> {
+        self.call("plugin:window|close").await
+    }
+
+    /// Destroys the window.
+    ///
+    /// Unlike [`close`](Window::close), it doesn't emit
+    /// a close request, so the window is closed even if
+    /// it is [listened](on_close_requested) to.
+    #[inline]
+    pub async fn destroy(&self) -> Result<(), Error> {
+        self.call("plugin:window|destroy").await
+    }
+
+    /// Shows the window.
+    #[inline]
+    pub async fn show(&self) -> Result<(), Error> {
+        self.call("plugin:window|show").await
+    }
+
+    /// Hides the window.
+    #[inline]
+    pub async fn hide(&self) -> Result<(), Error> {
+        self.call("plugin:window|hide").await
+    }
+
+    /// Brings the window to the front and focuses it.
+    #[inline]
+    pub async fn set_focus(&self) -> Result<(), Error> {
+        self.call("plugin:window|set_focus").await
+    }
+
+    /// Sets the window title.
+    #[inline]
+    pub async fn set_title(&self, title: &str) -> Result<(), Error> {
+        let args = ext::wargs(self.label.as_ref(), &JsValue::from_str(title));
+        invoke("plugin:window|set_title").with_args(args).await?;
+        Ok(())
+    }
+
+    /// Returns the window title.
+    #[inline]
+    pub async fn title(&self) -> Result<String, Error> {
+        let title = self.query("plugin:window|title").await?;
+        title.as_string().ok_or_else(|| unexpected("string"))
+    }
+
+    /// Checks whether the window is maximized.
+    #[inline]
+    pub async fn is_maximized(&self) -> Result<bool, Error> {
+        self.query_bool("plugin:window|is_maximized").await
+    }
+
+    /// Checks whether the window is minimized.
+    #[inline]
+    pub async fn is_minimized(&self) -> Result<bool, Error> {
+        self.query_bool("plugin:window|is_minimized").await
+    }
+
+    /// Checks whether the window is visible.
+    #[inline]
+    pub async fn is_visible(&self) -> Result<bool, Error> {
+        self.query_bool("plugin:window|is_visible").await
+    }
+
+    async fn call(&self, cmd: &str) -> Result<(), Error> {
+        self.query(cmd).await?;
+        Ok(())
+    }
+
+    async fn query(&self, cmd: &str) -> Result<JsValue, Error> {
+        let args = ext::wargs(self.label.as_ref(), &JsValue::UNDEFINED);
+        invoke(cmd).with_args(args).await
+    }
+
+    async fn query_bool(&self, cmd: &str) -> Result<bool, Error> {
+        let value = self.query(cmd).await?;
+        value.as_bool().ok_or_else(|| unexpected("boolean"))
+    }
+}
+
+/// A payload of the file drop event.
+///
+/// See [`on_drag_drop`] for details.
+#[derive(Clone, Debug, Default)]
+pub struct DragDrop {
+    paths: Vec<String>,
+    position: Position,
+}
+
+impl DragDrop {
+    fn from_payload(payload: &JsValue) -> Self {
+        let paths = Reflect::get(payload, &JsValue::from_str("paths"))
+            .ok()
+            .and_then(|paths| paths.dyn_into::<Array>().ok())
+            .map(|paths| paths.iter().filter_map(|path| path.as_string()).collect())
+            .unwrap_or_default();
+
+        let position = Reflect::get(payload, &JsValue::from_str("position"))
+            .map(|position| Position::from_value(&position))
+            .unwrap_or_default();
+
+        Self { paths, position }
+    }
+
+    /// Returns paths of the dropped files.
+    #[inline]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Returns owned paths of the dropped files.
+    #[inline]
+    pub fn into_paths(self) -> Vec<String> {
+        self.paths
+    }
+
+    /// Returns the drop position.
+    #[inline]
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+/// A physical position.
+///
+/// For drop events, it's relative to the top-left corner
+/// of the window. For move events, it's the position
+/// of the window on the screen.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Position {
+    /// The horizontal coordinate.
+    pub x: f64,
+
+    /// The vertical coordinate.
+    pub y: f64,
+}
+
+impl Position {
+    fn from_value(value: &JsValue) -> Self {
+        let get = |key| {
+            Reflect::get(value, &JsValue::from_str(key))
+                .ok()
+                .and_then(|v| v.as_f64())
+                .unwrap_or_default()
+        };
+
+        Self {
+            x: get("x"),
+            y: get("y"),
+        }
+    }
+}
+
+/// A physical size of the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Size {
+    /// The width in pixels.
+    pub width: u32,
+
+    /// The height in pixels.
+    pub height: u32,
+}
+
+impl Size {
+    fn from_value(value: &JsValue) -> Self {
+        let get = |key| {
+            Reflect::get(value, &JsValue::from_str(key))
+                .ok()
+                .and_then(|v| v.as_f64())
+                .map_or(0, |v| v as u32)
+        };
+
+        Self {
+            width: get("width"),
+            height: get("height"),
+        }
+    }
+}
+
+/// Listens to files dropped on the current window.
+///
+/// The handler is called with the [payload](DragDrop) of the
+/// `tauri://drag-drop` event, which contains paths of the
+/// dropped files and the drop position. Use the returned
+/// [`Unlisten`] to stop listening.
+///
+/// If the current window label is unknown, the handler
+/// receives drop events of all windows.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::window};
+///
+/// let unlisten = window::on_drag_drop(|drop| {
+///     for path in drop.paths() {
+///         console::log!("dropped", path);
+///     }
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Capabilities
+///
+/// Like other event listeners, it requires
+/// the `core:event:default` permission to be enabled.
+#[inline]
+pub async fn on_drag_drop<F>(mut handler: F) -> Result<Unlisten, Error>
+where
+    F: FnMut(DragDrop) + 'static,
+{
+    listen(events::DRAG_DROP, move |payload| {
+        handler(DragDrop::from_payload(payload));
+    })
+    .await
+}
+
+/// Listens to close requests of the current window.
+///
+/// The handler is called when the user tries to close
+/// the window, for example, with the title bar button.
+///
+/// Note that while the listener is registered, tauri
+/// doesn't close the window by itself, so it can be
+/// used to confirm closing or save unsaved changes.
+/// To actually close the window, call its
+/// [`destroy`](Window::destroy) method from the handler
+/// or [unlisten](Unlisten::unlisten) and close it again.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {
+///     tauri_wasm::window::{self, Window},
+///     wasm_bindgen_futures::spawn_local,
+/// };
+///
+/// let unlisten = window::on_close_requested(|| {
+///     spawn_local(async {
+///         // save the document before closing
+///         if let Some(window) = Window::current() {
+///             _ = window.destroy().await;
+///         }
+///     });
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn on_close_requested<F>(mut handler: F) -> Result<Unlisten, Error>
+where
+    F: FnMut() + 'static,
+{
+    listen(events::CLOSE_REQUESTED, move |_| handler()).await
+}
+
+/// Listens to the current window gaining focus.
+#[inline]
+pub async fn on_focus<F>(mut handler: F) -> Result<Unlisten, Error>
+where
+    F: FnMut() + 'static,
+{
+    listen(events::FOCUS, move |_| handler()).await
+}
+
+/// Listens to the current window losing focus.
+#[inline]
+pub async fn on_blur<F>(mut handler: F) -> Result<Unlisten, Error>
+where
+    F: FnMut() + 'static,
+{
+    listen(events::BLUR, move |_| handler()).await
+}
+
+/// Listens to resizing of the current window.
+///
+/// The handler is called with the new
+/// physical size of the window.
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {gloo::console, tauri_wasm::window};
+///
+/// let unlisten = window::on_resize(|size| {
+///     console::log!("resized", size.width, size.height);
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub async fn on_resize<F>(mut handler: F) -> Result<Unlisten, Error>
+where
+    F: FnMut(Size) + 'static,
+{
+    listen(events::RESIZE, move |payload| {
+        handler(Size::from_value(payload));
+    })
+    .await
+}
+
+/// Listens to moving of the current window.
+///
+/// The handler is called with the new physical
+/// position of the window on the screen.
+#[inline]
+pub async fn on_move<F>(mut handler: F) -> Result<Unlisten, Error>
+where
+    F: FnMut(Position) + 'static,
+{
+    listen(events::MOVE, move |payload| {
+        handler(Position::from_value(payload));
+    })
+    .await
+}
+
+async fn listen<F>(event: &'static str, mut handler: F) -> Result<Unlisten, Error>
+where
+    F: FnMut(&JsValue) + 'static,
+{
+    // window events are delivered only to
+    // listeners targeting the window itself
+    let listen = event::listen(event, move |ev| handler(ev.payload()));
+    match current_window_label() {
+        Some(label) => listen.to(EventTarget::window(label)).await,
+        None => listen.await,
+    }
+}
+
+/// Names of the window events.
+///
+/// The backend emits these events to a window when
+/// its state changes. The names can be used with the
+/// [`listen`](crate::event::listen) function directly,
+/// but for common events there are typed helpers like
+/// [`on_resize`].
+///
+/// # Example
+///
+/// ```
+/// # async fn e() -> Result<(), tauri_wasm::Error> {
+/// use {
+///     gloo::console,
+///     tauri_wasm::{event, window::events},
+/// };
+///
+/// let unlisten = event::listen(events::THEME_CHANGED, |ev| {
+///     console::log!("theme changed", ev.payload());
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub mod events {
+    /// The window has been resized.
+    pub const RESIZE: &str = "tauri://resize";
+
+    /// The window has been moved.
+    pub const MOVE: &str = "tauri://move";
+
+    /// The window has been requested to close.
+    pub const CLOSE_REQUESTED: &str = "tauri://close-requested";
+
+    /// The window has been destroyed.
+    pub const DESTROYED: &str = "tauri://destroyed";
+
+    /// The window has gained focus.
+    pub const FOCUS: &str = "tauri://focus";
+
+    /// The window has lost focus.
+    pub const BLUR: &str = "tauri://blur";
+
+    /// The scale factor of the window has changed.
+    pub const SCALE_CHANGE: &str = "tauri://scale-change";
+
+    /// The system theme has changed.
+    pub const THEME_CHANGED: &str = "tauri://theme-changed";
+
+    /// Files have been dragged over the window.
+    pub const DRAG_ENTER: &str = "tauri://drag-enter";
+
+    /// Files are being dragged over the window.
+    pub const DRAG_OVER: &str = "tauri://drag-over";
+
+    /// Files have been dropped on the window.
+    pub const DRAG_DROP: &str = "tauri://drag-drop";
+
+    /// Files have been dragged out of the window.
+    pub const DRAG_LEAVE: &str = "tauri://drag-leave";
+}
+
+fn unexpected(ty: &str) -> Error {
+    let e = TypeError::new(&format!("the window command result is not a {ty}"));
+    Error::js(JsValue::from(e))
+}